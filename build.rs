@@ -1,4 +1,6 @@
 fn main() {
-    println!("cargo:rustc-link-search=native=./lib");
-    println!("cargo:rustc-link-lib=static=Packet");
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
+        println!("cargo:rustc-link-search=native=./lib");
+        println!("cargo:rustc-link-lib=static=Packet");
+    }
 }
@@ -0,0 +1,89 @@
+//! ARP requests and replies, used to find hosts on our own IPv4 subnet
+//! and the hardware addresses frames to them are sent to.
+
+use std::net::Ipv4Addr;
+
+use pnet::{
+    packet::{
+        arp::{ArpHardwareTypes, ArpOperation, ArpOperations, ArpPacket, MutableArpPacket},
+        ethernet::{EtherTypes, EthernetPacket, MutableEthernetPacket},
+        Packet,
+    },
+    util::MacAddr,
+};
+
+use crate::config::{ARP_LEN, ETHERNET_HEADER_LEN};
+
+/// The part of an ARP packet we care about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arp {
+    pub operation: ArpOperation,
+    pub sender_mac: MacAddr,
+    pub sender_ip: Ipv4Addr,
+    pub target_ip: Ipv4Addr,
+}
+
+/// Broadcasts who-has `target_ip`.
+pub fn request(src_mac: MacAddr, src_ip: Ipv4Addr, target_ip: Ipv4Addr) -> Vec<u8> {
+    build(
+        ArpOperations::Request,
+        src_mac,
+        src_ip,
+        MacAddr::broadcast(),
+        target_ip,
+    )
+}
+
+/// Tells `dest_mac` that `src_ip` is at `src_mac`.
+pub fn reply(src_mac: MacAddr, src_ip: Ipv4Addr, dest_mac: MacAddr, dest_ip: Ipv4Addr) -> Vec<u8> {
+    build(ArpOperations::Reply, src_mac, src_ip, dest_mac, dest_ip)
+}
+
+pub fn parse(frame: &[u8]) -> Option<Arp> {
+    let eth = EthernetPacket::new(frame)?;
+    if eth.get_ethertype() != EtherTypes::Arp {
+        return None;
+    }
+
+    let arp = ArpPacket::new(eth.payload())?;
+    Some(Arp {
+        operation: arp.get_operation(),
+        sender_mac: arp.get_sender_hw_addr(),
+        sender_ip: arp.get_sender_proto_addr(),
+        target_ip: arp.get_target_proto_addr(),
+    })
+}
+
+fn build(
+    operation: ArpOperation,
+    src_mac: MacAddr,
+    src_ip: Ipv4Addr,
+    dest_mac: MacAddr,
+    dest_ip: Ipv4Addr,
+) -> Vec<u8> {
+    let mut packet_buf = vec![0_u8; ETHERNET_HEADER_LEN + ARP_LEN];
+
+    let mut arp_packet = MutableArpPacket::new(&mut packet_buf[ETHERNET_HEADER_LEN..]).unwrap();
+    arp_packet.set_hardware_type(ArpHardwareTypes::Ethernet);
+    arp_packet.set_protocol_type(EtherTypes::Ipv4);
+    arp_packet.set_hw_addr_len(6);
+    arp_packet.set_proto_addr_len(4);
+    arp_packet.set_operation(operation);
+    arp_packet.set_sender_hw_addr(src_mac);
+    arp_packet.set_sender_proto_addr(src_ip);
+    // unknown until answered
+    arp_packet.set_target_hw_addr(if operation == ArpOperations::Request {
+        MacAddr::zero()
+    } else {
+        dest_mac
+    });
+    arp_packet.set_target_proto_addr(dest_ip);
+
+    let mut eth_header =
+        MutableEthernetPacket::new(&mut packet_buf[..ETHERNET_HEADER_LEN]).unwrap();
+    eth_header.set_destination(dest_mac);
+    eth_header.set_source(src_mac);
+    eth_header.set_ethertype(EtherTypes::Arp);
+
+    packet_buf
+}
@@ -1,25 +1,106 @@
 use clap::Parser;
+use syn_port_scanner::{
+    toml_parser::Profile, PortSpec, ScanConfig, Selector, Timing, TimingTemplate,
+};
 
 #[derive(Parser)]
-#[command(author, about, long_about = None, next_line_help = false)]
-struct Cli {
+#[command(
+    author,
+    about,
+    long_about = None,
+    next_line_help = false,
+    after_help = "退出码: 0 成功, 2 配置错误, 3 网卡错误, 4 权限不足, 5 I/O错误"
+)]
+pub struct Cli {
     ///执行配置文件的路径，格式为toml
-    profile_path: String,
+    pub profile_path: String,
 
     /// 输出结果文件的路径，输出格式为toml格式
-    /// 默认值为./output.toml
-    output_path: Option<String>,
+    #[arg(default_value = "./output.toml")]
+    pub output_path: String,
+
+    /// 网卡名称(如 eth0)、网卡上的任一IP地址或 auto(按默认路由选择)
+    /// 会覆盖配置文件中的网卡设置
+    #[arg(short = 'i', long)]
+    pub interface: Option<Selector>,
+
+    /// 端口，格式同 nmap -p，如 22,80,8000-8100,U:53,T:3389 或 -1024、60000-
+    /// 没有前缀的端口为TCP，会替换配置文件中所有目标的端口，exclude 仍然生效
+    #[arg(short = 'p', long, allow_hyphen_values = true)]
+    pub ports: Option<PortSpec>,
+
+    /// 时序模板: paranoid, sneaky, polite, normal, aggressive, insane (或0-5)
+    /// 会覆盖配置文件中的时序设置
+    #[arg(short = 'T', long)]
+    pub timing: Option<TimingTemplate>,
+
+    /// 每秒最多发送的数据包数
+    #[arg(long, value_parser = parse_rate)]
+    pub rate: Option<f64>,
+
+    /// 未响应端口的重传次数
+    #[arg(long)]
+    pub retries: Option<u8>,
+
+    /// 跳过主机发现，所有目标都视为存活 (同 nmap -Pn)
+    #[arg(long = "skip-discovery", visible_alias = "Pn")]
+    pub skip_discovery: bool,
+
+    /// 只做主机发现，输出存活主机列表 (同 nmap -sn)
+    #[arg(
+        long = "discovery-only",
+        visible_alias = "sn",
+        conflicts_with = "skip_discovery"
+    )]
+    pub discovery_only: bool,
 }
 
-pub fn get_args() -> (String, String) {
-    let args = Cli::parse();
-    let profile_path = args.profile_path;
+impl Cli {
+    /// 命令行参数优先于配置文件
+    pub fn apply_timing(&self, timing: &mut Timing) {
+        if let Some(template) = self.timing {
+            *timing = template.timing();
+        }
+        if let Some(rate) = self.rate {
+            timing.rate = Some(rate);
+        }
+        if let Some(retries) = self.retries {
+            timing.retries = retries;
+        }
+    }
+
+    pub fn apply_interface(&self, config: &mut ScanConfig) {
+        if let Some(interface) = &self.interface {
+            config.interface = interface.clone();
+        }
+    }
+
+    pub fn apply_ports(&self, profile: &mut Profile) {
+        if let Some(ports) = &self.ports {
+            profile.set_ports(ports);
+        }
+    }
 
-    let output_path = if let Some(p) = args.output_path {
-        p
-    } else {
-        String::from("./output.toml")
-    };
+    /// 命令行开关只会打开配置文件中的设置，不会关闭
+    pub fn apply_discovery(&self, config: &mut ScanConfig) {
+        if self.skip_discovery {
+            config.skip_discovery = true;
+            config.discovery_only = false;
+        }
+        if self.discovery_only {
+            config.discovery_only = true;
+            config.skip_discovery = false;
+        }
+    }
+}
+
+fn parse_rate(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(rate) if rate > 0.0 => Ok(rate),
+        _ => Err(format!("速率必须为正数: {}", s)),
+    }
+}
 
-    (profile_path, output_path)
+pub fn get_args() -> Cli {
+    Cli::parse()
 }
@@ -1,11 +1,16 @@
 mod ports;
+mod services;
 
 pub const IPV4_HEADER_LEN: usize = 20;
 
-// pub const IPV6_HEADER_LEN: usize = 40;
+pub const IPV6_HEADER_LEN: usize = 40;
 
 pub const ETHERNET_HEADER_LEN: usize = 14;
 
 pub const ICMP_ECHO_REQUEST_LEN: usize = 8;
 
-pub use ports::{get_port_name, get_ports_known};
+pub const UDP_HEADER_LEN: usize = 8;
+
+pub const ARP_LEN: usize = 28;
+
+pub use services::{Service, Services};
@@ -2,7 +2,9 @@ use indexmap::IndexMap;
 use lazy_static::lazy_static;
 
 lazy_static! {
-    /// Nmap top-N ports
+    /// Nmap TCP ports, most frequently open first. Only the order of the
+    /// frequencies is kept, not the ratios themselves, load an nmap-services
+    /// file for those and for other protocols.
     ///
     /// Use following commands to get list
     /// curl -fsSL  https://raw.githubusercontent.com/nmap/nmap/master/nmap-services | awk '$2~/tcp$/' | sort -r -k3 | awk '{print $2" " $1}' | sed 's/\/tcp//' | awk '{print "("$1", \""$2"\"),"}'
@@ -14,7 +16,7 @@ lazy_static! {
         m.insert(21, "ftp");
         m.insert(22, "ssh");
         m.insert(25, "smtp");
-        m.insert(3389, "ms-wbt-sertver");
+        m.insert(3389, "ms-wbt-server");
         m.insert(110, "pop3");
         m.insert(445, "microsoft-ds");
         m.insert(139, "netbios-ssn");
@@ -83,7 +85,7 @@ lazy_static! {
         m.insert(444, "snpp");
         m.insert(9999, "abyss");
         m.insert(5009, "airport-admin");
-        m.insert(7070, "realsertver");
+        m.insert(7070, "realserver");
         m.insert(5190, "aol");
         m.insert(3000, "ppp");
         m.insert(5432, "postgresql");
@@ -119,7 +121,7 @@ lazy_static! {
         m.insert(1048, "neod2");
         m.insert(2967, "symantec-av");
         m.insert(1053, "remote-as");
-        m.insert(3703, "adobesertver-3");
+        m.insert(3703, "adobeserver-3");
         m.insert(1056, "vfo");
         m.insert(1065, "syscomlan");
         m.insert(1064, "jstel");
@@ -144,7 +146,7 @@ lazy_static! {
         m.insert(1038, "mtqp");
         m.insert(2601, "zebra");
         m.insert(1, "tcpmux");
-        m.insert(7000, "afs3-filesertver");
+        m.insert(7000, "afs3-fileserver");
         m.insert(1066, "fpo-fns");
         m.insert(1069, "cognex-insight");
         m.insert(625, "apple-xsrvr-admin");
@@ -164,7 +166,7 @@ lazy_static! {
         m.insert(2161, "apc-agent");
         m.insert(6002, "X11:2");
         m.insert(1080, "socks");
-        m.insert(2401, "cvspsertver");
+        m.insert(2401, "cvspserver");
         m.insert(4045, "lockd");
         m.insert(902, "iss-realsecure");
         m.insert(7937, "nsrexecd");
@@ -201,7 +203,7 @@ lazy_static! {
         m.insert(1352, "lotusnotes");
         m.insert(24, "priv-mail");
         m.insert(3269, "globalcatLDAPssl");
-        m.insert(1111, "lmsocialsertver");
+        m.insert(1111, "lmsocialserver");
         m.insert(407, "timbuktu");
         m.insert(500, "isakmp");
         m.insert(20, "ftp-data");
@@ -221,7 +223,7 @@ lazy_static! {
         m.insert(1023, "netvenuechat");
         m.insert(1068, "instl_bootc");
         m.insert(222, "rsh-spx");
-        m.insert(7100, "font-sertvice");
+        m.insert(7100, "font-service");
         m.insert(888, "accessbuilder");
         m.insert(563, "snews");
         m.insert(1717, "fj-hdnet");
@@ -243,7 +245,7 @@ lazy_static! {
         m.insert(4662, "edonkey");
         m.insert(2065, "dlsrpn");
         m.insert(2010, "search");
-        m.insert(42, "namesertver");
+        m.insert(42, "nameserver");
         m.insert(9535, "man");
         m.insert(2602, "ripd");
         m.insert(3333, "dec-notes");
@@ -265,7 +267,7 @@ lazy_static! {
         m.insert(16993, "amt-soap-https");
         m.insert(16992, "amt-soap-http");
         m.insert(5226, "hp-status");
-        m.insert(5225, "hp-sertver");
+        m.insert(5225, "hp-server");
         m.insert(32769, "filenet-rpc");
         m.insert(3283, "netassistant");
         m.insert(1052, "ddt");
@@ -326,7 +328,7 @@ lazy_static! {
         m.insert(8007, "ajp12");
         m.insert(3005, "deslogin");
         m.insert(1248, "hermes");
-        m.insert(2500, "rtssertv");
+        m.insert(2500, "rtsserv");
         m.insert(4242, "vrml-multi-use");
         m.insert(1097, "sunclustermgr");
         m.insert(9009, "pichat");
@@ -348,7 +350,7 @@ lazy_static! {
         m.insert(13783, "netbackup");
         m.insert(6969, "acmsoda");
         m.insert(3071, "csd-mgmt-port");
-        m.insert(5269, "xmpp-sertver");
+        m.insert(5269, "xmpp-server");
         m.insert(5222, "xmpp-client");
         m.insert(1085, "webobjects");
         m.insert(1046, "wfremotertm");
@@ -444,14 +446,14 @@ lazy_static! {
         m.insert(1061, "kiosk");
         m.insert(1107, "isoipsigport-2");
         m.insert(1106, "isoipsigport-1");
-        m.insert(9500, "ismsertver");
+        m.insert(9500, "ismserver");
         m.insert(20222, "ipulse-ics");
         m.insert(7778, "interwise");
         m.insert(1077, "imgames");
         m.insert(1310, "husky");
         m.insert(2119, "gsigatekeeper");
         m.insert(2492, "groove");
-        m.insert(1070, "gmrupdatesertv");
+        m.insert(1070, "gmrupdateserv");
         m.insert(20000, "dnp");
         m.insert(8400, "cvd");
         m.insert(1272, "cspmlockmgr");
@@ -467,9 +469,9 @@ lazy_static! {
         m.insert(32776, "sometimes-rpc15");
         m.insert(1999, "tcp-id-port");
         m.insert(212, "anet");
-        m.insert(2020, "xinupagesertver");
+        m.insert(2020, "xinupageserver");
         m.insert(6003, "X11:3");
-        m.insert(7002, "afs3-prsertver");
+        m.insert(7002, "afs3-prserver");
         m.insert(2998, "iss-realsec");
         m.insert(50002, "iiimsf");
         m.insert(3372, "msdtc");
@@ -488,7 +490,7 @@ lazy_static! {
         m.insert(7007, "afs3-bos");
         m.insert(458, "appleqtc");
         m.insert(9666, "zoomcp");
-        m.insert(8100, "xprint-sertver");
+        m.insert(8100, "xprint-server");
         m.insert(3737, "xpanel");
         m.insert(5298, "presence");
         m.insert(1152, "winpoplanmess");
@@ -545,7 +547,7 @@ lazy_static! {
         m.insert(32779, "sometimes-rpc21");
         m.insert(1021, "exp1");
         m.insert(32777, "sometimes-rpc17");
-        m.insert(2021, "sertvexec");
+        m.insert(2021, "servexec");
         m.insert(32778, "sometimes-rpc19");
         m.insert(616, "sco-sysmgr");
         m.insert(666, "doom");
@@ -583,16 +585,16 @@ lazy_static! {
         m.insert(3221, "xnm-clear-text");
         m.insert(3826, "wormux");
         m.insert(9200, "wap-wsp");
-        m.insert(2608, "wag-sertvice");
+        m.insert(2608, "wag-service");
         m.insert(4279, "vrml-multi-use");
         m.insert(7025, "vmsvc-2");
         m.insert(11111, "vce");
-        m.insert(3527, "besertver-msg-q");
+        m.insert(3527, "beserver-msg-q");
         m.insert(1151, "unizensus");
         m.insert(8200, "trivnet1");
         m.insert(8300, "tmi");
         m.insert(6689, "tsa");
-        m.insert(9878, "kca-sertvice");
+        m.insert(9878, "kca-service");
         m.insert(10009, "swdtp-sv");
         m.insert(8800, "sunwebadmin");
         m.insert(5730, "unieng");
@@ -614,11 +616,11 @@ lazy_static! {
         m.insert(1114, "mini-sql");
         m.insert(6510, "mcer-port");
         m.insert(3905, "mupdate");
-        m.insert(8383, "m2msertvices");
+        m.insert(8383, "m2mservices");
         m.insert(3914, "listcrt-port-2");
-        m.insert(3971, "lanrevsertver");
+        m.insert(3971, "lanrevserver");
         m.insert(3809, "apocd");
-        m.insert(5033, "jtnetd-sertver");
+        m.insert(5033, "jtnetd-server");
         m.insert(7676, "imqbrokerd");
         m.insert(3517, "802-11-iapp");
         m.insert(4900, "hfcs");
@@ -643,7 +645,7 @@ lazy_static! {
         m.insert(5087, "biotic");
         m.insert(1119, "bnetgame");
         m.insert(1117, "ardus-mtrns");
-        m.insert(4848, "appsertv-http");
+        m.insert(4848, "appserv-http");
         m.insert(7800, "asr");
         m.insert(16000, "fmsas");
         m.insert(3324, "active-net");
@@ -685,7 +687,7 @@ lazy_static! {
         m.insert(44443, "coldfusion-auth");
         m.insert(1984, "bigbrother");
         m.insert(2068, "avocentkvm");
-        m.insert(7004, "afs3-kasertver");
+        m.insert(7004, "afs3-kaserver");
         m.insert(4343, "unicall");
         m.insert(416, "silverplatter");
         m.insert(2038, "objectmanager");
@@ -734,7 +736,7 @@ lazy_static! {
         m.insert(1556, "veritas_pbx");
         m.insert(13724, "vnetd");
         m.insert(1141, "mxomss");
-        m.insert(1233, "univ-appsertver");
+        m.insert(1233, "univ-appserver");
         m.insert(8765, "ultraseek-http");
         m.insert(1137, "trim");
         m.insert(3963, "thrp");
@@ -743,7 +745,7 @@ lazy_static! {
         m.insert(3808, "sun-as-iiops-ca");
         m.insert(8686, "sun-as-jmxrmi");
         m.insert(3981, "starfish");
-        m.insert(2710, "sso-sertvice");
+        m.insert(2710, "sso-service");
         m.insert(3852, "sse-app-config");
         m.insert(3849, "spw-dnspreload");
         m.insert(3944, "sops");
@@ -752,7 +754,7 @@ lazy_static! {
         m.insert(1163, "sddp");
         m.insert(4164, "silverpeakcomm");
         m.insert(3820, "scp");
-        m.insert(6481, "sertvicetags");
+        m.insert(6481, "servicetags");
         m.insert(3731, "smap");
         m.insert(5081, "sdl-ets");
         m.insert(40000, "safetynetp");
@@ -760,7 +762,7 @@ lazy_static! {
         m.insert(4555, "rsip");
         m.insert(3863, "asap-tcp");
         m.insert(1287, "routematch");
-        m.insert(4430, "rsqlsertver");
+        m.insert(4430, "rsqlserver");
         m.insert(7744, "raqmon-pdu");
         m.insert(1812, "radius");
         m.insert(7913, "qo-secure");
@@ -768,7 +770,7 @@ lazy_static! {
         m.insert(1164, "qsm-proxy");
         m.insert(1165, "qsm-gui");
         m.insert(8019, "qbdb");
-        m.insert(10160, "qb-db-sertver");
+        m.insert(10160, "qb-db-server");
         m.insert(4658, "playsta2-app");
         m.insert(7878, "owms");
         m.insert(3304, "opsession-srvr");
@@ -785,7 +787,7 @@ lazy_static! {
         m.insert(3859, "nav-port");
         m.insert(3514, "must-p2p");
         m.insert(4949, "munin");
-        m.insert(4147, "vrxpsertvman");
+        m.insert(4147, "vrxpservman");
         m.insert(7900, "mevent");
         m.insert(5353, "mdns");
         m.insert(3931, "msr-plugin-port");
@@ -801,9 +803,9 @@ lazy_static! {
         m.insert(1113, "ltp-deepspace");
         m.insert(3969, "landmarks");
         m.insert(1132, "kvm-via-ip");
-        m.insert(1309, "jtag-sertver");
+        m.insert(1309, "jtag-server");
         m.insert(3848, "item");
-        m.insert(7281, "itactionsertver2");
+        m.insert(7281, "itactionserver2");
         m.insert(3907, "imoguia-port");
         m.insert(3972, "iconp");
         m.insert(3968, "ianywhere-dbns");
@@ -831,7 +833,7 @@ lazy_static! {
         m.insert(4143, "oidsr");
         m.insert(5868, "diameters");
         m.insert(8889, "ddi-tcp-2");
-        m.insert(12006, "dbisamsertver2");
+        m.insert(12006, "dbisamserver2");
         m.insert(1121, "rmpp");
         m.insert(3119, "d2000kernel");
         m.insert(8015, "cfg-cloud");
@@ -839,7 +841,7 @@ lazy_static! {
         m.insert(3824, "acp-policy");
         m.insert(1154, "resacommunity");
         m.insert(20002, "commtact-http");
-        m.insert(3888, "ciphire-sertv");
+        m.insert(3888, "ciphire-serv");
         m.insert(4009, "chimera-hwm");
         m.insert(5063, "csrpc");
         m.insert(3376, "cdbroker");
@@ -863,7 +865,7 @@ lazy_static! {
         m.insert(5909, "agma");
         m.insert(8766, "amcs");
         m.insert(5905, "asmgcs");
-        m.insert(1102, "adobesertver-1");
+        m.insert(1102, "adobeserver-1");
         m.insert(2800, "acc-raid");
         m.insert(2910, "tdaccess");
         m.insert(1594, "sixtrak");
@@ -926,7 +928,7 @@ lazy_static! {
         m.insert(953, "rndc");
         m.insert(8118, "privoxy");
         m.insert(9992, "issc");
-        m.insert(1270, "sssertver");
+        m.insert(1270, "ssserver");
         m.insert(27, "nsw-fe");
         m.insert(123, "ntp");
         m.insert(86, "mfcobol");
@@ -940,7 +942,7 @@ lazy_static! {
         m.insert(441, "decvms-sysmgt");
         m.insert(210, "z39.50");
         m.insert(6008, "X11:8");
-        m.insert(7003, "afs3-vlsertver");
+        m.insert(7003, "afs3-vlserver");
         m.insert(5803, "vnc-http-3");
         m.insert(1008, "ufsd");
         m.insert(556, "remotefs");
@@ -988,7 +990,7 @@ lazy_static! {
         m.insert(602, "xmlrpc-beep");
         m.insert(3456, "vat");
         m.insert(862, "twamp-control");
-        m.insert(600, "ipcsertver");
+        m.insert(600, "ipcserver");
         m.insert(2903, "extensisportfolio");
         m.insert(257, "fw1-mc-fwmodule");
         m.insert(1522, "rna-lm");
@@ -1003,7 +1005,7 @@ lazy_static! {
         m.insert(1357, "pegboard");
         m.insert(3632, "distccd");
         m.insert(3399, "sapeps");
-        m.insert(6050, "arcsertve");
+        m.insert(6050, "arcserve");
         m.insert(2201, "ats");
         m.insert(655, "tinc");
         m.insert(786, "concert");
@@ -1024,7 +1026,7 @@ lazy_static! {
         m.insert(1337, "waste");
         m.insert(1127, "supfiledbg");
         m.insert(2112, "kip");
-        m.insert(1414, "ibm-mqserties");
+        m.insert(1414, "ibm-mqseries");
         m.insert(2600, "zebrasrv");
         m.insert(621, "escp-ip");
         m.insert(606, "urm");
@@ -1064,7 +1066,7 @@ lazy_static! {
         m.insert(4035, "wap-push-http");
         m.insert(2312, "wanscaler");
         m.insert(3652, "vxcrnbuport");
-        m.insert(3280, "vs-sertver");
+        m.insert(3280, "vs-server");
         m.insert(4243, "vrml-multi-use");
         m.insert(4298, "vrml-multi-use");
         m.insert(4297, "vrml-multi-use");
@@ -1120,7 +1122,7 @@ lazy_static! {
         m.insert(9911, "sype-transport");
         m.insert(10006, "netapp-sync");
         m.insert(3923, "symb-sb-port");
-        m.insert(3930, "syam-websertver");
+        m.insert(3930, "syam-webserver");
         m.insert(1221, "sweetware-apps");
         m.insert(2973, "svnetworks");
         m.insert(3909, "surfcontrolcpa");
@@ -1188,7 +1190,7 @@ lazy_static! {
         m.insert(3532, "raven-rmp");
         m.insert(3799, "radius-dynauth");
         m.insert(3790, "quickbooksrds");
-        m.insert(3599, "quasar-sertver");
+        m.insert(3599, "quasar-server");
         m.insert(3850, "qtms-bootstrap");
         m.insert(4355, "qsnet-workst");
         m.insert(4358, "qsnet-nucl");
@@ -1216,7 +1218,7 @@ lazy_static! {
         m.insert(4025, "partimage");
         m.insert(3089, "ptk-alink");
         m.insert(9021, "panagolin-ident");
-        m.insert(30001, "pago-sertvices1");
+        m.insert(30001, "pago-services1");
         m.insert(8472, "otv");
         m.insert(5014, "onpsocket");
         m.insert(9990, "osm-appsrvr");
@@ -1248,16 +1250,16 @@ lazy_static! {
         m.insert(1196, "netmagic");
         m.insert(4407, "nacagent");
         m.insert(3520, "galileolog");
-        m.insert(3812, "neto-wol-sertver");
+        m.insert(3812, "neto-wol-server");
         m.insert(5012, "nsp");
         m.insert(10115, "netiq-endpt");
         m.insert(1615, "netbill-auth");
         m.insert(2902, "netaspi");
         m.insert(4118, "netscript");
         m.insert(2706, "ncdmirroring");
-        m.insert(2095, "nbx-sert");
+        m.insert(2095, "nbx-ser");
         m.insert(2096, "nbx-dir");
-        m.insert(3363, "nati-vi-sertver");
+        m.insert(3363, "nati-vi-server");
         m.insert(5137, "ctsd");
         m.insert(3795, "myblast");
         m.insert(8005, "mxi");
@@ -1312,7 +1314,7 @@ lazy_static! {
         m.insert(5052, "ita-manager");
         m.insert(1638, "ismc");
         m.insert(3419, "softaudit");
-        m.insert(2551, "isg-uda-sertver");
+        m.insert(2551, "isg-uda-server");
         m.insert(5908, "ipsma");
         m.insert(4029, "ip-qsig");
         m.insert(3603, "int-rcv-cntrl");
@@ -1320,7 +1322,7 @@ lazy_static! {
         m.insert(2082, "infowave");
         m.insert(1143, "imyx");
         m.insert(3602, "infiniswitchcl");
-        m.insert(1176, "indigo-sertver");
+        m.insert(1176, "indigo-server");
         m.insert(4100, "igo-incognito");
         m.insert(3486, "ifsf-hb-port");
         m.insert(6077, "iconstructsrv");
@@ -1385,20 +1387,20 @@ lazy_static! {
         m.insert(3728, "e-woa");
         m.insert(3621, "ep-nsp");
         m.insert(2291, "eapsp");
-        m.insert(5114, "ev-sertvices");
+        m.insert(5114, "ev-services");
         m.insert(7101, "elcn");
         m.insert(1315, "els");
         m.insert(2087, "eli");
         m.insert(5234, "eenet");
-        m.insert(1635, "edb-sertver1");
+        m.insert(1635, "edb-server1");
         m.insert(3263, "ecolor-imager");
         m.insert(4121, "e-builder");
-        m.insert(4602, "mtssertver");
+        m.insert(4602, "mtsserver");
         m.insert(2224, "efi-mg");
         m.insert(3949, "drip");
         m.insert(9131, "dddp");
         m.insert(3310, "dyna-access");
-        m.insert(3937, "dvbsertvdsc");
+        m.insert(3937, "dvbservdsc");
         m.insert(2253, "dtv-chan-req");
         m.insert(3882, "msdts1");
         m.insert(3831, "dvapps");
@@ -1418,11 +1420,11 @@ lazy_static! {
         m.insert(1173, "d-cinema-rrp");
         m.insert(22125, "dcap");
         m.insert(17500, "db-lsp");
-        m.insert(12005, "dbisamsertver1");
-        m.insert(6113, "daylitesertver");
+        m.insert(12005, "dbisamserver1");
+        m.insert(6113, "dayliteserver");
         m.insert(1973, "dlsrap");
         m.insert(3793, "dcsoftware");
-        m.insert(3637, "scsertvp");
+        m.insert(3637, "scservp");
         m.insert(8954, "cumulus-admin");
         m.insert(3742, "cst-port");
         m.insert(9667, "xmms2");
@@ -1431,7 +1433,7 @@ lazy_static! {
         m.insert(4300, "corelccam");
         m.insert(8445, "copy");
         m.insert(12865, "netperf");
-        m.insert(3365, "contentsertver");
+        m.insert(3365, "contentserver");
         m.insert(4665, "contclientms");
         m.insert(3190, "csvr-proxy");
         m.insert(3577, "config-port");
@@ -1453,13 +1455,13 @@ lazy_static! {
         m.insert(8880, "cddbp-alt");
         m.insert(2734, "ccs-software");
         m.insert(3505, "ccmcomm");
-        m.insert(3388, "cbsertver");
+        m.insert(3388, "cbserver");
         m.insert(3669, "casanswmgmt");
         m.insert(1871, "canocentral0");
         m.insert(8025, "ca-audit-da");
         m.insert(1958, "dxadmind");
         m.insert(3681, "bts-x73");
-        m.insert(3014, "broker_sertvice");
+        m.insert(3014, "broker_service");
         m.insert(8999, "bctp");
         m.insert(4415, "brcd-vr-req");
         m.insert(3414, "wip-port");
@@ -1474,7 +1476,7 @@ lazy_static! {
         m.insert(3989, "bv-queryengine");
         m.insert(3992, "bv-ds");
         m.insert(2302, "binderysupport");
-        m.insert(3415, "bcinamesertvice");
+        m.insert(3415, "bcinameservice");
         m.insert(1179, "b2n");
         m.insert(3946, "backupedge");
         m.insert(2203, "b2-runtime");
@@ -1501,13 +1503,13 @@ lazy_static! {
         m.insert(8023, "arca-api");
         m.insert(8474, "noteshare");
         m.insert(3879, "appss-lm");
-        m.insert(4087, "applussertvice");
+        m.insert(4087, "applusservice");
         m.insert(4112, "apple-vpns-rp");
         m.insert(6350, "adap");
         m.insert(9950, "apc-9950");
         m.insert(3506, "apc-3506");
         m.insert(3948, "apdap");
-        m.insert(3825, "ffsertver");
+        m.insert(3825, "ffserver");
         m.insert(2325, "ansysli");
         m.insert(1800, "ansys-lm");
         m.insert(1153, "c1222-acse");
@@ -1541,10 +1543,10 @@ lazy_static! {
         m.insert(4545, "worldscores");
         m.insert(2991, "wkstn-mon");
         m.insert(6065, "winpharaoh");
-        m.insert(1290, "winjasertver");
+        m.insert(1290, "winjaserver");
         m.insert(1559, "web2host");
-        m.insert(1317, "vrts-ipcsertver");
-        m.insert(5423, "virtualusert");
+        m.insert(1317, "vrts-ipcserver");
+        m.insert(5423, "virtualuser");
         m.insert(1707, "vdmplay");
         m.insert(5055, "unot");
         m.insert(1327, "ultrex");
@@ -1566,9 +1568,9 @@ lazy_static! {
         m.insert(5250, "soagateway");
         m.insert(1645, "sightline");
         m.insert(1303, "sftsrv");
-        m.insert(3636, "sertvistaitsm");
-        m.insert(1251, "sertvergraph");
-        m.insert(1243, "sertialgateway");
+        m.insert(3636, "servistaitsm");
+        m.insert(1251, "servergraph");
+        m.insert(1243, "serialgateway");
         m.insert(1291, "seagulllms");
         m.insert(1297, "sdproxy");
         m.insert(1200, "scol");
@@ -1640,7 +1642,7 @@ lazy_static! {
         m.insert(7099, "lazy-ptop");
         m.insert(2372, "lanmessenger");
         m.insert(1318, "krb5gatekeeper");
-        m.insert(1339, "kjtsitesertver");
+        m.insert(1339, "kjtsiteserver");
         m.insert(1276, "ivmanager");
         m.insert(11000, "irisa");
         m.insert(48619, "iqobject");
@@ -1677,7 +1679,7 @@ lazy_static! {
         m.insert(9909, "domaintime");
         m.insert(4022, "dnox");
         m.insert(1324, "delta-mcp");
-        m.insert(2584, "cyasertv");
+        m.insert(2584, "cyaserv");
         m.insert(2300, "cvmmon");
         m.insert(9287, "cumulus");
         m.insert(2806, "cspuni");
@@ -1706,7 +1708,7 @@ lazy_static! {
         m.insert(8403, "admind");
         m.insert(31416, "boinc");
         m.insert(22273, "wnn6");
-        m.insert(7005, "afs3-volsert");
+        m.insert(7005, "afs3-volser");
         m.insert(66, "sqlnet");
         m.insert(32786, "sometimes-rpc25");
         m.insert(32787, "sometimes-rpc27");
@@ -1753,7 +1755,7 @@ lazy_static! {
         m.insert(9991, "issa");
         m.insert(3049, "cfs");
         m.insert(1366, "netware-csp");
-        m.insert(1364, "ndm-sertver");
+        m.insert(1364, "ndm-server");
         m.insert(833, "netconfsoapbeep");
         m.insert(91, "mit-dov");
         m.insert(1680, "CarbonCopy");
@@ -1774,7 +1776,7 @@ lazy_static! {
         m.insert(649, "cadview-3d");
         m.insert(707, "borland-dsj");
         m.insert(68, "dhcpc");
-        m.insert(449, "as-sertvermap");
+        m.insert(449, "as-servermap");
         m.insert(664, "secure-aux-bus");
         m.insert(75, "priv-dial");
         m.insert(104, "acr-nema");
@@ -1803,7 +1805,7 @@ lazy_static! {
         m.insert(605, "soap-beep");
         m.insert(8076, "slnp");
         m.insert(1651, "shiva_confsrvr");
-        m.insert(1178, "skksertv");
+        m.insert(1178, "skkserv");
         m.insert(6401, "crystalenterprise");
         m.insert(761, "kpasswd");
         m.insert(5145, "rmonitor_secure");
@@ -1846,7 +1848,7 @@ lazy_static! {
         m.insert(1356, "cuillamartin");
         m.insert(5232, "sgi-dgl");
         m.insert(1484, "confluent");
-        m.insert(450, "tsertver");
+        m.insert(450, "tserver");
         m.insert(1991, "stun-p2");
         m.insert(1988, "tr-rsrb-p2");
         m.insert(1523, "cichild-lm");
@@ -1887,7 +1889,7 @@ lazy_static! {
         m.insert(27665, "Trinoo_Master");
         m.insert(1475, "taligent-lm");
         m.insert(97, "swift-rvf");
-        m.insert(633, "sertvstat");
+        m.insert(633, "servstat");
         m.insert(560, "rmonitor");
         m.insert(799, "controlit");
         m.insert(7009, "afs3-rmtsys");
@@ -1900,7 +1902,7 @@ lazy_static! {
         m.insert(1527, "tlisrv");
         m.insert(723, "omfs");
         m.insert(1466, "oceansoft-lm");
-        m.insert(1486, "nms_topo_sertv");
+        m.insert(1486, "nms_topo_serv");
         m.insert(1650, "nkd");
         m.insert(991, "nas");
         m.insert(832, "netconfsoaphttp");
@@ -1925,7 +1927,7 @@ lazy_static! {
         m.insert(1369, "gv-us");
         m.insert(402, "genie");
         m.insert(103, "gppitnp");
-        m.insert(1372, "fc-sert");
+        m.insert(1372, "fc-ser");
         m.insert(704, "elcsd");
         m.insert(854, "dlep");
         m.insert(8892, "seosload");
@@ -1947,7 +1949,7 @@ lazy_static! {
         m.insert(1381, "apple-licman");
         m.insert(35, "priv-print");
         m.insert(6588, "analogx");
-        m.insert(5530, "sdsertv");
+        m.insert(5530, "sdserv");
         m.insert(3141, "vmodem");
         m.insert(670, "vacdsm-sws");
         m.insert(656, "spmp");
@@ -1965,7 +1967,7 @@ lazy_static! {
         m.insert(997, "maitrd");
         m.insert(505, "mailbox-lm");
         m.insert(1496, "liberty-lm");
-        m.insert(637, "lansertver");
+        m.insert(637, "lanserver");
         m.insert(213, "ipx");
         m.insert(1412, "innosys");
         m.insert(1515, "ifor-protocol");
@@ -1976,7 +1978,7 @@ lazy_static! {
         m.insert(644, "dwr");
         m.insert(675, "dctp");
         m.insert(1467, "csdmbase");
-        m.insert(454, "contentsertver");
+        m.insert(454, "contentserver");
         m.insert(622, "collaborator");
         m.insert(1476, "clvm-cfg");
         m.insert(1373, "chromagrafx");
@@ -2002,7 +2004,7 @@ lazy_static! {
         m.insert(6145, "statsci2-lm");
         m.insert(133, "statsrv");
         m.insert(362, "srssend");
-        m.insert(118, "sqlsertv");
+        m.insert(118, "sqlserv");
         m.insert(193, "srmp");
         m.insert(115, "sftp");
         m.insert(1549, "shivahose");
@@ -2065,7 +2067,7 @@ lazy_static! {
         m.insert(1499, "fhc");
         m.insert(7006, "afs3-errors");
         m.insert(1439, "eicon-x25");
-        m.insert(1438, "eicon-sertver");
+        m.insert(1438, "eicon-server");
         m.insert(8770, "apple-iphoto");
         m.insert(853, "domain-s");
         m.insert(196, "dn6-smm-red");
@@ -2113,14 +2115,14 @@ lazy_static! {
         m.insert(1362, "timeflies");
         m.insert(712, "tbrpf");
         m.insert(1437, "tabula");
-        m.insert(2027, "shadowsertver");
+        m.insert(2027, "shadowserver");
         m.insert(1368, "screencast");
         m.insert(1531, "rap-listen");
         m.insert(645, "pssc");
         m.insert(65301, "pcanywhere");
         m.insert(260, "openport");
         m.insert(536, "opalis-rdv");
-        m.insert(764, "omsertv");
+        m.insert(764, "omserv");
         m.insert(698, "olsr");
         m.insert(607, "nqs");
         m.insert(1667, "netview-aix-7");
@@ -2137,9 +2139,9 @@ lazy_static! {
         m.insert(1456, "dca");
         m.insert(1479, "dberegister");
         m.insert(355, "datex-asn");
-        m.insert(763, "cyclesertv");
+        m.insert(763, "cycleserv");
         m.insert(1472, "csdm");
-        m.insert(453, "creativesertver");
+        m.insert(453, "creativeserver");
         m.insert(759, "con");
         m.insert(437, "comscm");
         m.insert(2432, "codasrv");
@@ -2148,7 +2150,7 @@ lazy_static! {
         m.insert(1544, "aspeclmd");
         m.insert(1511, "3l-l1");
         m.insert(1538, "3ds-lm");
-        m.insert(346, "zsertv");
+        m.insert(346, "zserv");
         m.insert(173, "xyplex-mux");
         m.insert(54, "xns-ch");
         m.insert(56, "xns-auth");
@@ -2161,7 +2163,7 @@ lazy_static! {
         m.insert(1470, "uaiact");
         m.insert(13715, "netbackup");
         m.insert(13714, "netbackup");
-        m.insert(267, "td-sertvice");
+        m.insert(267, "td-service");
         m.insert(1419, "timbuktu-srv3");
         m.insert(1418, "timbuktu-srv2");
         m.insert(1407, "dbsa-lm");
@@ -2295,7 +2297,7 @@ lazy_static! {
         m.insert(2019, "whosockami");
         m.insert(516, "videotex");
         m.insert(522, "ulp");
-        m.insert(2028, "submitsertver");
+        m.insert(2028, "submitserver");
         m.insert(150, "sql-net");
         m.insert(572, "sonar");
         m.insert(596, "smsd");
@@ -2322,7 +2324,7 @@ lazy_static! {
         m.insert(1449, "peport");
         m.insert(1480, "pacerforum");
         m.insert(1473, "openmath");
-        m.insert(184, "ocsertver");
+        m.insert(184, "ocserver");
         m.insert(1672, "netview-aix-12");
         m.insert(1671, "netview-aix-11");
         m.insert(1670, "netview-aix-10");
@@ -2337,7 +2339,7 @@ lazy_static! {
         m.insert(414, "infoseek");
         m.insert(535, "iiop");
         m.insert(432, "iasd");
-        m.insert(479, "iafsertver");
+        m.insert(479, "iafserver");
         m.insert(473, "hybrid-pop");
         m.insert(151, "hems");
         m.insert(1542, "gridgen-elmd");
@@ -2351,7 +2353,7 @@ lazy_static! {
         m.insert(284, "corerjd");
         m.insert(542, "commerce");
         m.insert(370, "codaauth2");
-        m.insert(2016, "bootsertver");
+        m.insert(2016, "bootserver");
         m.insert(248, "bhfhs");
         m.insert(1491, "anynetgateway");
         m.insert(44123, "z-wave-tunnel");
@@ -2423,7 +2425,7 @@ lazy_static! {
         m.insert(4095, "xtgui");
         m.insert(3646, "xss-srv-port");
         m.insert(3510, "xss-port");
-        m.insert(3722, "xsertveraid");
+        m.insert(3722, "xserveraid");
         m.insert(2459, "community");
         m.insert(3651, "xrpc-registry");
         m.insert(14500, "xpra");
@@ -2435,7 +2437,7 @@ lazy_static! {
         m.insert(9092, "XmlIpcRegSvc");
         m.insert(5344, "xkotodrcp");
         m.insert(2341, "xiostatus");
-        m.insert(6116, "xtlsertv");
+        m.insert(6116, "xtlserv");
         m.insert(2157, "xnds");
         m.insert(165, "xns-courier");
         m.insert(6936, "xsmsvc");
@@ -2459,8 +2461,8 @@ lazy_static! {
         m.insert(2805, "wta-wsp-s");
         m.insert(4537, "wssauthsvc");
         m.insert(9762, "tungsten-http");
-        m.insert(5007, "wsm-sertver-ssl");
-        m.insert(5006, "wsm-sertver");
+        m.insert(5007, "wsm-server-ssl");
+        m.insert(5006, "wsm-server");
         m.insert(5358, "wsdapi-s");
         m.insert(4879, "wsdl-event");
         m.insert(6114, "wrspice");
@@ -2502,7 +2504,7 @@ lazy_static! {
         m.insert(3111, "wsynch");
         m.insert(3427, "websphere-snmp");
         m.insert(5321, "bsfsvr-zn-ssl");
-        m.insert(5320, "bsfsertver-zn");
+        m.insert(5320, "bsfserver-zn");
         m.insert(3702, "ws-discovery");
         m.insert(2907, "webmethods-b2b");
         m.insert(8991, "https-wmap");
@@ -2525,7 +2527,7 @@ lazy_static! {
         m.insert(9203, "wap-wsp-wtp-s");
         m.insert(2949, "wap-pushsecure");
         m.insert(2948, "wap-push");
-        m.insert(6626, "wago-sertvice");
+        m.insert(6626, "wago-service");
         m.insert(37472, "3gpp-w1ap");
         m.insert(8199, "vvr-data");
         m.insert(4145, "vvr-control");
@@ -2645,7 +2647,7 @@ lazy_static! {
         m.insert(3676, "va-pacbase");
         m.insert(24249, "vista-4gl");
         m.insert(1631, "visitview");
-        m.insert(6672, "vision_sertver");
+        m.insert(6672, "vision_server");
         m.insert(6673, "vision_elmd");
         m.insert(3601, "visinet-gui");
         m.insert(5046, "vpm-udp");
@@ -2674,13 +2676,13 @@ lazy_static! {
         m.insert(2994, "veritas-vis2");
         m.insert(2993, "veritas-vis1");
         m.insert(2802, "veritas-tcp1");
-        m.insert(1885, "vrtstrapsertver");
+        m.insert(1885, "vrtstrapserver");
         m.insert(14149, "vrts-tdd");
         m.insert(13786, "nomdb");
         m.insert(10100, "itap-ddtp");
         m.insert(9284, "visd");
         m.insert(14150, "vcscmd");
-        m.insert(10107, "bctp-sertver");
+        m.insert(10107, "bctp-server");
         m.insert(4032, "vrts-auth-port");
         m.insert(2821, "vrts-at-port");
         m.insert(3207, "vx-auth-port");
@@ -2714,7 +2716,7 @@ lazy_static! {
         m.insert(37654, "unisys-eportal");
         m.insert(1979, "unisql-java");
         m.insert(9629, "uniport");
-        m.insert(2357, "unihub-sertver");
+        m.insert(2357, "unihub-server");
         m.insert(1889, "unify-adapter");
         m.insert(3314, "uohost");
         m.insert(3313, "uorb");
@@ -2727,7 +2729,7 @@ lazy_static! {
         m.insert(8953, "ub-dns-control");
         m.insert(1910, "ultrabac");
         m.insert(13894, "ucontrol");
-        m.insert(372, "ulistsertv");
+        m.insert(372, "ulistserv");
         m.insert(3720, "ufastro-instr");
         m.insert(1382, "udt_os");
         m.insert(2542, "udrawgraph");
@@ -2785,7 +2787,7 @@ lazy_static! {
         m.insert(1906, "tpmd");
         m.insert(22351, "codemeter-cmwan");
         m.insert(2158, "touchnetplus");
-        m.insert(5153, "toruxsertver");
+        m.insert(5153, "toruxserver");
         m.insert(3885, "topflow-ssl");
         m.insert(24465, "tonidods");
         m.insert(3040, "tomato-springs");
@@ -2793,7 +2795,7 @@ lazy_static! {
         m.insert(8066, "toad-bi-appsrvr");
         m.insert(474, "tn-tl-w1");
         m.insert(2739, "tn-timing");
-        m.insert(3308, "tns-sertver");
+        m.insert(3308, "tns-server");
         m.insert(590, "tns-cml");
         m.insert(3309, "tns-adv");
         m.insert(7902, "tnos-dp");
@@ -2810,7 +2812,7 @@ lazy_static! {
         m.insert(2915, "tksocket");
         m.insert(1965, "tivoli-npm");
         m.insert(3459, "integral");
-        m.insert(3160, "tip-app-sertver");
+        m.insert(3160, "tip-app-server");
         m.insert(3754, "timestenbroker");
         m.insert(3243, "timelot");
         m.insert(10261, "tile-ml");
@@ -2822,7 +2824,7 @@ lazy_static! {
         m.insert(7548, "tidp");
         m.insert(1832, "tht-treasure");
         m.insert(28080, "thor-engine");
-        m.insert(3805, "tcpdatasertver");
+        m.insert(3805, "tcpdataserver");
         m.insert(16789, "cadsisvr");
         m.insert(8320, "tnp-discover");
         m.insert(8321, "tnp");
@@ -2908,7 +2910,7 @@ lazy_static! {
         m.insert(2060, "teleniumdaemon");
         m.insert(6083, "miami-bcast");
         m.insert(9668, "tec5-sdctp");
-        m.insert(3552, "tasertver");
+        m.insert(3552, "taserver");
         m.insert(1814, "tdp-suite");
         m.insert(1977, "tcoaddressbook");
         m.insert(2576, "tclprodebugger");
@@ -2924,10 +2926,10 @@ lazy_static! {
         m.insert(3579, "ttat3lb");
         m.insert(2306, "tappi-boxnet");
         m.insert(1450, "dwf");
-        m.insert(7015, "talon-websertver");
+        m.insert(7015, "talon-webserver");
         m.insert(7012, "talon-engine");
         m.insert(7011, "talon-disc");
-        m.insert(22763, "talikasertver");
+        m.insert(22763, "talikaserver");
         m.insert(2156, "trp");
         m.insert(2493, "talarian-mqs");
         m.insert(4019, "talarian-mcast5");
@@ -2945,9 +2947,9 @@ lazy_static! {
         m.insert(4094, "sysrqd");
         m.insert(3241, "sysorb");
         m.insert(6514, "syslog-tls");
-        m.insert(6418, "sysertverremote");
+        m.insert(6418, "syserverremote");
         m.insert(3734, "synel-data");
-        m.insert(2679, "syncsertverssl");
+        m.insert(2679, "syncserverssl");
         m.insert(4953, "dbsyncarbiter");
         m.insert(5008, "synapsis-edge");
         m.insert(2880, "synapse");
@@ -2979,7 +2981,7 @@ lazy_static! {
         m.insert(8989, "sunwebadmins");
         m.insert(6787, "smc-admin");
         m.insert(6483, "SunVTS-RMI");
-        m.insert(3867, "dzoglsertver");
+        m.insert(3867, "dzoglserver");
         m.insert(3866, "dzdaemon");
         m.insert(1860, "sunscalar-svc");
         m.insert(1870, "sunscalar-dns");
@@ -2995,7 +2997,7 @@ lazy_static! {
         m.insert(11164, "suncacao-csa");
         m.insert(3708, "sun-as-iiops");
         m.insert(4850, "sun-as-nodeagt");
-        m.insert(7677, "sun-usert-https");
+        m.insert(7677, "sun-user-https");
         m.insert(16959, "subseven");
         m.insert(247, "subntbcst_tftp");
         m.insert(3478, "stun");
@@ -3021,7 +3023,7 @@ lazy_static! {
         m.insert(3615, "start-network");
         m.insert(5777, "dali-port");
         m.insert(2154, "stdptc");
-        m.insert(45966, "ssr-sertvermgr");
+        m.insert(45966, "ssr-servermgr");
         m.insert(17235, "ssh-mgmt");
         m.insert(3018, "srvc_registry");
         m.insert(38800, "sruth");
@@ -3102,7 +3104,7 @@ lazy_static! {
         m.insert(6455, "skip-cert-recv");
         m.insert(3874, "sixxsconfig");
         m.insert(3611, "six-degrees");
-        m.insert(2629, "sitarasertver");
+        m.insert(2629, "sitaraserver");
         m.insert(2630, "sitaramgmt");
         m.insert(166, "s-net");
         m.insert(5059, "sds");
@@ -3151,7 +3153,7 @@ lazy_static! {
         m.insert(6444, "sge_qmaster");
         m.insert(6445, "sge_execd");
         m.insert(5634, "xprtld");
-        m.insert(5636, "sfm-db-sertver");
+        m.insert(5636, "sfm-db-server");
         m.insert(5635, "sfmsso");
         m.insert(6343, "sflow");
         m.insert(4546, "sf-lm");
@@ -3159,7 +3161,7 @@ lazy_static! {
         m.insert(5568, "sdt");
         m.insert(4057, "wfm");
         m.insert(24666, "sdtvwcam");
-        m.insert(21221, "aigairsertver");
+        m.insert(21221, "aigairserver");
         m.insert(6488, "sun-sr-jmx");
         m.insert(6484, "sun-sr-jms");
         m.insert(6486, "sun-sr-iiops");
@@ -3171,14 +3173,14 @@ lazy_static! {
         m.insert(7690, "sovd");
         m.insert(2603, "ripngd");
         m.insert(4787, "sia-ctrl-plane");
-        m.insert(2367, "sertvice-ctrl");
-        m.insert(9212, "sertverviewdbms");
-        m.insert(9213, "sertverstart");
+        m.insert(2367, "service-ctrl");
+        m.insert(9212, "serverviewdbms");
+        m.insert(9213, "serverstart");
         m.insert(5445, "smbdirect");
         m.insert(45824, "dai-shell");
-        m.insert(8351, "sertver-find");
+        m.insert(8351, "server-find");
         m.insert(13711, "netbackup");
-        m.insert(4076, "sertaph");
+        m.insert(4076, "seraph");
         m.insert(5099, "sentlm-srv2srv");
         m.insert(2316, "sent-lm");
         m.insert(3588, "sentinel");
@@ -3220,16 +3222,16 @@ lazy_static! {
         m.insert(3143, "seaview");
         m.insert(7784, "s-bfd");
         m.insert(2342, "manage-exec");
-        m.insert(2309, "sdsertver");
+        m.insert(2309, "sdserver");
         m.insert(2705, "sds-admin");
         m.insert(2310, "sdclient");
         m.insert(2384, "sd-request");
         m.insert(6315, "scup");
-        m.insert(5343, "kfsertver");
+        m.insert(5343, "kfserver");
         m.insert(9899, "sctp-tunneling");
         m.insert(5168, "scte30");
         m.insert(5167, "scte104");
-        m.insert(3927, "natdatasertvice");
+        m.insert(3927, "natdataservice");
         m.insert(266, "sst");
         m.insert(2577, "scipticslsrvr");
         m.insert(5307, "sco-aip");
@@ -3304,7 +3306,7 @@ lazy_static! {
         m.insert(8270, "robot-remote");
         m.insert(5693, "rbsystem");
         m.insert(25471, "rna");
-        m.insert(2416, "rmtsertver");
+        m.insert(2416, "rmtserver");
         m.insert(5994, "rms-agent");
         m.insert(9208, "rjcdb-vcards");
         m.insert(7810, "rbt-wanopt");
@@ -3318,7 +3320,7 @@ lazy_static! {
         m.insert(2057, "rich-cp");
         m.insert(6148, "ricardo-lm");
         m.insert(3296, "rib-slm");
-        m.insert(4410, "itwo-sertver");
+        m.insert(4410, "itwo-server");
         m.insert(4684, "rfid-rp1");
         m.insert(8230, "rexecj");
         m.insert(5842, "reversion");
@@ -3388,7 +3390,7 @@ lazy_static! {
         m.insert(2774, "rbakcup2");
         m.insert(2773, "rbakcup1");
         m.insert(6099, "raxa-mgmt");
-        m.insert(2167, "raw-sertial");
+        m.insert(2167, "raw-serial");
         m.insert(2714, "raventdm");
         m.insert(2713, "raventbs");
         m.insert(3533, "raven-rdp");
@@ -3426,7 +3428,7 @@ lazy_static! {
         m.insert(9222, "teamcoherence");
         m.insert(5083, "qfp");
         m.insert(5082, "qcp");
-        m.insert(45825, "qdb2sertvice");
+        m.insert(45825, "qdb2service");
         m.insert(2612, "qpasa-agent");
         m.insert(6980, "qolyester");
         m.insert(5689, "qmvideo");
@@ -3448,7 +3450,7 @@ lazy_static! {
         m.insert(4329, "publiqare-sync");
         m.insert(320, "ptp-general");
         m.insert(319, "ptp-event");
-        m.insert(597, "ptcnamesertvice");
+        m.insert(597, "ptcnameservice");
         m.insert(3453, "pscupd");
         m.insert(4457, "prRegister");
         m.insert(2303, "proxy-gateway");
@@ -3471,7 +3473,7 @@ lazy_static! {
         m.insert(34964, "profinet-cm");
         m.insert(4749, "profilemac");
         m.insert(8032, "pro-ed");
-        m.insert(4182, "pcptcpsertvice");
+        m.insert(4182, "pcptcpservice");
         m.insert(1283, "productinfo");
         m.insert(1778, "prodigy-intrnet");
         m.insert(3248, "procos-lm");
@@ -3480,7 +3482,7 @@ lazy_static! {
         m.insert(3650, "prismiq-plugin");
         m.insert(3133, "prism-deploy");
         m.insert(2618, "priority-e-com");
-        m.insert(4168, "pslicsert");
+        m.insert(4168, "pslicser");
         m.insert(10631, "printopia");
         m.insert(1392, "iclpv-pm");
         m.insert(3910, "prnrequest");
@@ -3493,12 +3495,12 @@ lazy_static! {
         m.insert(2315, "precise-sft");
         m.insert(3607, "precise-i3");
         m.insert(5630, "precise-comm");
-        m.insert(4455, "prchat-usert");
-        m.insert(4456, "prchat-sertver");
+        m.insert(4455, "prchat-user");
+        m.insert(4456, "prchat-server");
         m.insert(1587, "pra_elmd");
         m.insert(28001, "pqsp");
         m.insert(5134, "ppactivation");
-        m.insert(13224, "powwow-sertver");
+        m.insert(13224, "powwow-server");
         m.insert(13223, "powwow-client");
         m.insert(5507, "psl-management");
         m.insert(2443, "powerclientcsf");
@@ -3511,7 +3513,7 @@ lazy_static! {
         m.insert(7787, "popup-reminders");
         m.insert(6771, "plysrv-https");
         m.insert(6770, "plysrv-http");
-        m.insert(3055, "policysertver");
+        m.insert(3055, "policyserver");
         m.insert(2487, "pns");
         m.insert(16310, "pduncs");
         m.insert(16311, "pdefmns");
@@ -3526,7 +3528,7 @@ lazy_static! {
         m.insert(32400, "plex");
         m.insert(4351, "plcy-net-svcs");
         m.insert(3934, "sunfm-port");
-        m.insert(3933, "plbsertve-port");
+        m.insert(3933, "plbserve-port");
         m.insert(4659, "playsta2-lob");
         m.insert(1819, "plato-lm");
         m.insert(5586, "att-mt-sms");
@@ -3551,7 +3553,7 @@ lazy_static! {
         m.insert(3177, "phonex-port");
         m.insert(3347, "phoenix-rpc");
         m.insert(6124, "pnbs");
-        m.insert(2350, "psbsertver");
+        m.insert(2350, "psbserver");
         m.insert(3208, "pfu-prcallback");
         m.insert(2520, "pvsw");
         m.insert(2441, "pvsw-inet");
@@ -3562,7 +3564,7 @@ lazy_static! {
         m.insert(4313, "perrla");
         m.insert(5312, "permabit-cs");
         m.insert(4066, "pmas");
-        m.insert(345, "pawsertv");
+        m.insert(345, "pawserv");
         m.insert(9630, "peoctlr");
         m.insert(9631, "peocoll");
         m.insert(6817, "pentbox-sim");
@@ -3612,10 +3614,10 @@ lazy_static! {
         m.insert(4312, "pscl-mgt");
         m.insert(4989, "parallel");
         m.insert(7216, "PS-Capture-Pro");
-        m.insert(3348, "pangolin-lasert");
+        m.insert(3348, "pangolin-laser");
         m.insert(3095, "panasas");
         m.insert(6657, "palcom-disc");
-        m.insert(30002, "pago-sertvices2");
+        m.insert(30002, "pago-services2");
         m.insert(7237, "pads");
         m.insert(3435, "pacom");
         m.insert(2246, "pc-mta-addrmap");
@@ -3628,7 +3630,7 @@ lazy_static! {
         m.insert(3034, "osmosis-aeea");
         m.insert(40853, "ortec-disc");
         m.insert(11103, "origo-sync");
-        m.insert(3274, "ordinox-sertver");
+        m.insert(3274, "ordinox-server");
         m.insert(3355, "ordinox-dbase");
         m.insert(3078, "orbix-cfg-ssl");
         m.insert(3075, "orbix-locator");
@@ -3646,7 +3648,7 @@ lazy_static! {
         m.insert(2481, "giop");
         m.insert(5155, "asctrl-agent");
         m.insert(5575, "ora-oap");
-        m.insert(3718, "opus-sertvices");
+        m.insert(3718, "opus-services");
         m.insert(22005, "optohost004");
         m.insert(22004, "optohost004");
         m.insert(22003, "optohost003");
@@ -3660,7 +3662,7 @@ lazy_static! {
         m.insert(19191, "opsec-uaa");
         m.insert(3433, "alta-smp");
         m.insert(5724, "omsdk");
-        m.insert(2400, "opequus-sertver");
+        m.insert(2400, "opequus-server");
         m.insert(7629, "xdas");
         m.insert(6640, "ovsdb");
         m.insert(2389, "ovsessionmgr");
@@ -3688,7 +3690,7 @@ lazy_static! {
         m.insert(5755, "openmailg");
         m.insert(5768, "openmailpxy");
         m.insert(4743, "openhpid");
-        m.insert(9008, "ogs-sertver");
+        m.insert(9008, "ogs-server");
         m.insert(9007, "ogs-client");
         m.insert(3187, "odi-port");
         m.insert(20014, "opendeploy");
@@ -3702,12 +3704,12 @@ lazy_static! {
         m.insert(9994, "palace-3");
         m.insert(9993, "palace-2");
         m.insert(8767, "core-of-source");
-        m.insert(4304, "owsertver");
+        m.insert(4304, "owserver");
         m.insert(2428, "ott");
         m.insert(2199, "onehome-help");
         m.insert(2198, "onehome-remote");
         m.insert(2185, "onbase-dds");
-        m.insert(4428, "omvisertver");
+        m.insert(4428, "omviserver");
         m.insert(4429, "omviagent");
         m.insert(4162, "omstopology");
         m.insert(4395, "omnivisionesx");
@@ -3731,16 +3733,16 @@ lazy_static! {
         m.insert(11174, "oemcacao-rmi");
         m.insert(11172, "oemcacao-jmxmp");
         m.insert(13706, "netbackup");
-        m.insert(3523, "odeumsertvlink");
+        m.insert(3523, "odeumservlink");
         m.insert(429, "ocs_amu");
         m.insert(2697, "oce-snmp-trap");
         m.insert(18186, "ohsc");
-        m.insert(3442, "connect-sertver");
+        m.insert(3442, "connect-server");
         m.insert(3441, "connect-client");
         m.insert(29167, "otmp");
-        m.insert(36602, "obsertvium-agent");
+        m.insert(36602, "observium-agent");
         m.insert(7030, "op-probe");
-        m.insert(1894, "o2sertver-port");
+        m.insert(1894, "o2server-port");
         m.insert(28000, "nxlmd");
         m.insert(126, "unitary");
         m.insert(4420, "nvm-express");
@@ -3752,10 +3754,10 @@ lazy_static! {
         m.insert(8711, "nvc");
         m.insert(10810, "nmc-disc");
         m.insert(45001, "asmps");
-        m.insert(5415, "ns-sertver");
+        m.insert(5415, "ns-server");
         m.insert(4453, "nssalertmgr");
         m.insert(359, "tenebris_nts");
-        m.insert(3266, "ns-cfg-sertver");
+        m.insert(3266, "ns-cfg-server");
         m.insert(36424, "nq-ap");
         m.insert(2868, "npep-messaging");
         m.insert(7724, "nsdeepfreezectl");
@@ -3777,7 +3779,7 @@ lazy_static! {
         m.insert(5627, "ninaf");
         m.insert(4088, "npsp");
         m.insert(2210, "noaaport");
-        m.insert(2244, "nmssertver");
+        m.insert(2244, "nmsserver");
         m.insert(2817, "nmsigport");
         m.insert(10111, "nmea-onenet");
         m.insert(10110, "nmea-0183");
@@ -3809,17 +3811,17 @@ lazy_static! {
         m.insert(3165, "newgenpay");
         m.insert(6133, "nbt-wol");
         m.insert(2614, "neveroffline");
-        m.insert(2585, "netx-sertver");
+        m.insert(2585, "netx-server");
         m.insert(4702, "netxms-sync");
         m.insert(4701, "netxms-mgmt");
         m.insert(2586, "netx-agent");
         m.insert(3203, "netwatcher-mon");
         m.insert(3204, "netwatcher-db");
         m.insert(4460, "ntske");
-        m.insert(16361, "netsertialext2");
-        m.insert(16367, "netsertialext3");
-        m.insert(16360, "netsertialext1");
-        m.insert(16368, "netsertialext4");
+        m.insert(16361, "netserialext2");
+        m.insert(16367, "netserialext3");
+        m.insert(16360, "netserialext1");
+        m.insert(16368, "netserialext4");
         m.insert(4159, "nss");
         m.insert(170, "print-srv");
         m.insert(2293, "npdbgmngr");
@@ -3834,7 +3836,7 @@ lazy_static! {
         m.insert(3196, "ncu-2");
         m.insert(3195, "ncu-1");
         m.insert(2411, "netwave-ap-mgmt");
-        m.insert(2788, "fryesertv");
+        m.insert(2788, "fryeserv");
         m.insert(4127, "unikeypro");
         m.insert(6777, "ntz-tracker");
         m.insert(6778, "ntz-p2p-storage");
@@ -3859,7 +3861,7 @@ lazy_static! {
         m.insert(2485, "netobjects1");
         m.insert(6842, "netmo-http");
         m.insert(6841, "netmo-default");
-        m.insert(3149, "nm-game-sertver");
+        m.insert(3149, "nm-game-server");
         m.insert(3148, "nm-game-admin");
         m.insert(3150, "nm-asses-admin");
         m.insert(3151, "nm-assessor");
@@ -3916,14 +3918,14 @@ lazy_static! {
         m.insert(6317, "nav-data-cmd");
         m.insert(4361, "nacnl");
         m.insert(2895, "natuslink");
-        m.insert(3753, "nattysertver");
+        m.insert(3753, "nattyserver");
         m.insert(2343, "nati-logos");
         m.insert(3015, "nati-dstp");
         m.insert(1790, "nmsp");
         m.insert(3950, "namemunge");
         m.insert(6363, "ndn");
         m.insert(9286, "n2receive");
-        m.insert(9285, "n2h2sertver");
+        m.insert(9285, "n2h2server");
         m.insert(7282, "mzca-action");
         m.insert(6446, "mysql-proxy");
         m.insert(2273, "mysql-im");
@@ -3938,7 +3940,7 @@ lazy_static! {
         m.insert(6622, "mcftp");
         m.insert(5354, "mdnsresponder");
         m.insert(7742, "msss");
-        m.insert(2305, "mt-scalesertver");
+        m.insert(2305, "mt-scaleserver");
         m.insert(2791, "mtport-regist");
         m.insert(8115, "mtl8000-matrix");
         m.insert(3122, "vtr-emulator");
@@ -3986,7 +3988,7 @@ lazy_static! {
         m.insert(7606, "mipi-debug");
         m.insert(5136, "minotaur-sa");
         m.insert(4069, "minger");
-        m.insert(21554, "dfsertver");
+        m.insert(21554, "dfserver");
         m.insert(7391, "mindfilesys");
         m.insert(9445, "mindarray-ca");
         m.insert(2180, "mc-gt-srv");
@@ -3996,7 +3998,7 @@ lazy_static! {
         m.insert(3008, "midnight-tech");
         m.insert(7013, "microtalon-dis");
         m.insert(7014, "microtalon-com");
-        m.insert(5362, "sertverwsd2");
+        m.insert(5362, "serverwsd2");
         m.insert(6601, "mstmg-sstp");
         m.insert(1512, "wins");
         m.insert(5356, "ms-smlbiz");
@@ -4014,14 +4016,14 @@ lazy_static! {
         m.insert(4679, "mgesupervision");
         m.insert(4680, "mgemanagement");
         m.insert(6509, "mgcs-mfp-port");
-        m.insert(2266, "mfsertver");
+        m.insert(2266, "mfserver");
         m.insert(6382, "metatude-mds");
         m.insert(2230, "queueadm");
         m.insert(6390, "metaedit-ws");
         m.insert(6370, "metaedit-se");
         m.insert(6360, "metaedit-mu");
         m.insert(393, "dis");
-        m.insert(2311, "messagesertvice");
+        m.insert(2311, "messageservice");
         m.insert(8787, "msgsrvr");
         m.insert(18, "msp");
         m.insert(8786, "msgclnt");
@@ -4034,7 +4036,7 @@ lazy_static! {
         m.insert(4552, "menandmice-mon");
         m.insert(11211, "memcache");
         m.insert(3569, "mbg-ctrl");
-        m.insert(4883, "wmlsertver");
+        m.insert(4883, "wmlserver");
         m.insert(3571, "megardsvr-port");
         m.insert(2944, "megaco-h248");
         m.insert(2945, "h248-binary");
@@ -4053,7 +4055,7 @@ lazy_static! {
         m.insert(3235, "mdap-port");
         m.insert(5598, "mctfeed");
         m.insert(3115, "mctet-master");
-        m.insert(3117, "mctet-jsertv");
+        m.insert(3117, "mctet-jserv");
         m.insert(3116, "mctet-gateway");
         m.insert(3331, "mcs-messaging");
         m.insert(3332, "mcs-mailsvr");
@@ -4120,7 +4122,7 @@ lazy_static! {
         m.insert(5254, "logcabin");
         m.insert(5058, "locus-disc");
         m.insert(4044, "ltp");
-        m.insert(3591, "gtrack-sertver");
+        m.insert(3591, "gtrack-server");
         m.insert(3592, "gtrack-ne");
         m.insert(1903, "linkname");
         m.insert(5062, "na-localise");
@@ -4130,12 +4132,12 @@ lazy_static! {
         m.insert(2466, "lbf");
         m.insert(6200, "lm-x");
         m.insert(8208, "lm-webwatcher");
-        m.insert(8207, "lm-ssertver");
+        m.insert(8207, "lm-sserver");
         m.insert(8204, "lm-perfworks");
         m.insert(31620, "lm-mon");
         m.insert(8205, "lm-instmgr");
         m.insert(8206, "lm-dta");
-        m.insert(3278, "lkcmsertver");
+        m.insert(3278, "lkcmserver");
         m.insert(2145, "lv-pici");
         m.insert(2143, "lv-jc");
         m.insert(2147, "lv-auth");
@@ -4150,7 +4152,7 @@ lazy_static! {
         m.insert(8191, "limnerpressure");
         m.insert(517, "talk");
         m.insert(4670, "light");
-        m.insert(7365, "lcm-sertver");
+        m.insert(7365, "lcm-server");
         m.insert(3028, "LiebDevMgmt_DM");
         m.insert(3027, "LiebDevMgmt_C");
         m.insert(3029, "LiebDevMgmt_A");
@@ -4158,7 +4160,7 @@ lazy_static! {
         m.insert(1886, "leoip");
         m.insert(11430, "lsdp");
         m.insert(374, "legent-2");
-        m.insert(2212, "leecopossertver");
+        m.insert(2212, "leecoposserver");
         m.insert(3407, "ldap-admin");
         m.insert(2816, "lbc-watchdog");
         m.insert(2779, "lbc-sync");
@@ -4177,7 +4179,7 @@ lazy_static! {
         m.insert(4432, "l-acoustics");
         m.insert(9747, "l5nas-parchan");
         m.insert(4371, "l2c-control");
-        m.insert(3360, "kv-sertver");
+        m.insert(3360, "kv-server");
         m.insert(3361, "kv-agent");
         m.insert(4331, "ktickets-rest");
         m.insert(40023, "k-patentssensor");
@@ -4189,7 +4191,7 @@ lazy_static! {
         m.insert(186, "kis");
         m.insert(30260, "kingdomsonline");
         m.insert(4566, "kwtc");
-        m.insert(584, "keysertver");
+        m.insert(584, "keyserver");
         m.insert(5696, "kmip");
         m.insert(6623, "ktelnet");
         m.insert(6620, "kftp-data");
@@ -4217,12 +4219,12 @@ lazy_static! {
         m.insert(3094, "rapidmq-reg");
         m.insert(3093, "rapidmq-center");
         m.insert(3777, "jibe-eb");
-        m.insert(1937, "jwsertver");
+        m.insert(1937, "jwserver");
         m.insert(1938, "jwclient");
-        m.insert(1939, "jvsertver");
+        m.insert(1939, "jvserver");
         m.insert(1940, "jvclient");
         m.insert(2097, "jetformpreview");
-        m.insert(1936, "jetcmesertver");
+        m.insert(1936, "jetcmeserver");
         m.insert(1810, "jerand-lm");
         m.insert(6244, "jeol-nsdtp-4");
         m.insert(6243, "jeol-nsdtp-3");
@@ -4242,7 +4244,7 @@ lazy_static! {
         m.insert(3472, "jaugsremotec-1");
         m.insert(7181, "janus-disc");
         m.insert(5034, "jtnetd-status");
-        m.insert(3627, "jamsertverport");
+        m.insert(3627, "jamserverport");
         m.insert(8091, "jamlink");
         m.insert(1578, "jacobus-lm");
         m.insert(5673, "jms");
@@ -4250,9 +4252,9 @@ lazy_static! {
         m.insert(4880, "hislip");
         m.insert(3258, "ivecon-port");
         m.insert(2828, "itm-lm");
-        m.insert(3719, "itelsertverport");
+        m.insert(3719, "itelserverport");
         m.insert(7478, "openit");
-        m.insert(7280, "itactionsertver1");
+        m.insert(7280, "itactionserver1");
         m.insert(1636, "isdc");
         m.insert(1637, "islc");
         m.insert(3775, "ispmmgr");
@@ -4274,7 +4276,7 @@ lazy_static! {
         m.insert(4073, "iRAPP");
         m.insert(32034, "iracinghelper");
         m.insert(18769, "ique");
-        m.insert(2527, "iqsertver");
+        m.insert(2527, "iqserver");
         m.insert(4593, "ipt-anri-anri");
         m.insert(4792, "unified-bus");
         m.insert(4791, "roce");
@@ -4330,10 +4332,10 @@ lazy_static! {
         m.insert(23001, "inovaport2");
         m.insert(23000, "inovaport1");
         m.insert(2716, "inova-ip-disco");
-        m.insert(3560, "inisertve-port");
+        m.insert(3560, "iniserve-port");
         m.insert(5597, "ininmessaging");
         m.insert(134, "ingres-net");
-        m.insert(38001, "ivs-inserttion");
+        m.insert(38001, "ivs-insertion");
         m.insert(38000, "ivs-database");
         m.insert(4067, "idp");
         m.insert(1428, "informatik-lm");
@@ -4355,7 +4357,7 @@ lazy_static! {
         m.insert(11320, "imip-channels");
         m.insert(8615, "imink");
         m.insert(585, "imap4-ssl");
-        m.insert(48128, "isnetsertv");
+        m.insert(48128, "isnetserv");
         m.insert(2239, "imagequery");
         m.insert(3596, "iw-mmogame");
         m.insert(2055, "iop");
@@ -4388,7 +4390,7 @@ lazy_static! {
         m.insert(31949, "iceedcp_rx");
         m.insert(2276, "ibridge-mgmt");
         m.insert(2275, "ibridge-data");
-        m.insert(1881, "ibm-mqserties2");
+        m.insert(1881, "ibm-mqseries2");
         m.insert(2078, "tpcsrvr");
         m.insert(3660, "can-nds-ssl");
         m.insert(3661, "can-ferret-ssl");
@@ -4401,7 +4403,7 @@ lazy_static! {
         m.insert(9089, "sqlexec-ssl");
         m.insert(9088, "sqlexec");
         m.insert(3667, "infoexch");
-        m.insert(3666, "esertver-pap");
+        m.insert(3666, "eserver-pap");
         m.insert(12003, "entextmed");
         m.insert(12004, "entextlow");
         m.insert(3539, "ibm-diradm-ssl");
@@ -4455,7 +4457,7 @@ lazy_static! {
         m.insert(3250, "hicp");
         m.insert(43438, "hmip-routing");
         m.insert(4884, "hivestor");
-        m.insert(4117, "hillrsertv");
+        m.insert(4117, "hillrserv");
         m.insert(2467, "high-criteria");
         m.insert(4148, "hhb-handheld");
         m.insert(18516, "heythings");
@@ -4492,7 +4494,7 @@ lazy_static! {
         m.insert(2186, "gtaua");
         m.insert(35356, "guttersnex");
         m.insert(3436, "gc-config");
-        m.insert(2152, "gtp-usert");
+        m.insert(2152, "gtp-user");
         m.insert(2123, "gtp-control");
         m.insert(1452, "gtegsc-lm");
         m.insert(4729, "gsmtap");
@@ -4552,7 +4554,7 @@ lazy_static! {
         m.insert(1859, "gammafetchsvr");
         m.insert(31765, "gamesmith-port");
         m.insert(2914, "gamelobby");
-        m.insert(3051, "galaxy-sertver");
+        m.insert(3051, "galaxy-server");
         m.insert(38201, "galaxy7-data");
         m.insert(8881, "galaxy4d");
         m.insert(4340, "gaia");
@@ -4563,7 +4565,7 @@ lazy_static! {
         m.insert(2731, "fyre-messanger");
         m.insert(286, "fxp");
         m.insert(3402, "fxaengine-net");
-        m.insert(3272, "usert-manager");
+        m.insert(3272, "user-manager");
         m.insert(1514, "fujitsu-dtcns");
         m.insert(3382, "fujitsu-neat");
         m.insert(1904, "fjicl-tep-c");
@@ -4575,7 +4577,7 @@ lazy_static! {
         m.insert(3488, "fs-rh-srv");
         m.insert(9217, "fsc-port");
         m.insert(4130, "fronet");
-        m.insert(7726, "freezexsertvice");
+        m.insert(7726, "freezexservice");
         m.insert(5556, "freeciv");
         m.insert(7244, "frc-hicp");
         m.insert(4319, "fox-skytale");
@@ -4598,7 +4600,7 @@ lazy_static! {
         m.insert(7018, "fisa-svc");
         m.insert(2673, "firstcall42");
         m.insert(4086, "ftsync");
-        m.insert(8043, "fs-sertver");
+        m.insert(8043, "fs-server");
         m.insert(8044, "fs-mgmt");
         m.insert(3192, "firemonrcc");
         m.insert(3729, "fksp-audit");
@@ -4625,10 +4627,10 @@ lazy_static! {
         m.insert(3225, "fcip-port");
         m.insert(2819, "fc-faultnotify");
         m.insert(4038, "fazzt-ptp");
-        m.insert(6417, "faxcomsertvice");
-        m.insert(347, "fatsertv");
-        m.insert(3047, "hlsertver");
-        m.insert(2495, "fast-rem-sertv");
+        m.insert(6417, "faxcomservice");
+        m.insert(347, "fatserv");
+        m.insert(3047, "hlserver");
+        m.insert(2495, "fast-rem-serv");
         m.insert(10081, "famdc");
         m.insert(38202, "fairview");
         m.insert(19790, "faircom-db");
@@ -4643,7 +4645,7 @@ lazy_static! {
         m.insert(3088, "xdtp");
         m.insert(3134, "ecp");
         m.insert(3639, "xap-ha");
-        m.insert(4309, "dsertver");
+        m.insert(4309, "dserver");
         m.insert(2755, "expresspay");
         m.insert(1928, "emsd-port");
         m.insert(5075, "pvaccess");
@@ -4666,7 +4668,7 @@ lazy_static! {
         m.insert(2221, "rockwell-csp1");
         m.insert(44818, "EtherNetIP-2");
         m.insert(34980, "ethercat");
-        m.insert(2380, "etcd-sertver");
+        m.insert(2380, "etcd-server");
         m.insert(2379, "etcd-client");
         m.insert(6107, "etc-control");
         m.insert(1772, "essweb-gw");
@@ -4675,7 +4677,7 @@ lazy_static! {
         m.insert(8266, "espeasy-p2p");
         m.insert(4023, "esnm-zoning");
         m.insert(3629, "escvpnet");
-        m.insert(9617, "erunbook_sertver");
+        m.insert(9617, "erunbook_server");
         m.insert(3679, "newton-dock");
         m.insert(3727, "e-mdu");
         m.insert(4942, "eq-office-4942");
@@ -4709,8 +4711,8 @@ lazy_static! {
         m.insert(20480, "emwavemsg");
         m.insert(23333, "elxmgmt");
         m.insert(3585, "emprise-lls");
-        m.insert(6322, "emp-sertver2");
-        m.insert(6321, "emp-sertver1");
+        m.insert(6322, "emp-server2");
+        m.insert(6321, "emp-server1");
         m.insert(4091, "ewinstaller");
         m.insert(4092, "ewdgs");
         m.insert(140, "emfis-data");
@@ -4728,7 +4730,7 @@ lazy_static! {
         m.insert(2526, "ema-sent-lm");
         m.insert(7700, "em7-secom");
         m.insert(7707, "sync-em7");
-        m.insert(2916, "elvin_sertver");
+        m.insert(2916, "elvin_server");
         m.insert(2917, "elvin_client");
         m.insert(4370, "elpro_tunnel");
         m.insert(6515, "elipse-rec");
@@ -4761,7 +4763,7 @@ lazy_static! {
         m.insert(3524, "ecmport");
         m.insert(6160, "ecmp");
         m.insert(17729, "ea");
-        m.insert(3711, "edb-sertver2");
+        m.insert(3711, "edb-server2");
         m.insert(45678, "eba");
         m.insert(2168, "easy-soft-mux");
         m.insert(3328, "egptlm");
@@ -4774,7 +4776,7 @@ lazy_static! {
         m.insert(3246, "dvt-system");
         m.insert(3247, "dvt-data");
         m.insert(4191, "dsmipv6");
-        m.insert(4028, "dtsertver-port");
+        m.insert(4028, "dtserver-port");
         m.insert(3489, "dtp-dia");
         m.insert(4556, "dtn-bundle-tcp");
         m.insert(5684, "coaps");
@@ -4792,10 +4794,10 @@ lazy_static! {
         m.insert(3685, "dsx-agent");
         m.insert(2193, "drwcs");
         m.insert(4427, "drizzle");
-        m.insert(1930, "driveappsertver");
+        m.insert(1930, "driveappserver");
         m.insert(8913, "dragonfly");
-        m.insert(7021, "dpsertveadmin");
-        m.insert(7020, "dpsertve");
+        m.insert(7021, "dpserveadmin");
+        m.insert(7020, "dpserve");
         m.insert(5719, "dpm-agent");
         m.insert(5565, "hpe-dp-bura");
         m.insert(5245, "downtools");
@@ -4810,7 +4812,7 @@ lazy_static! {
         m.insert(8567, "oap-admin");
         m.insert(3567, "oap");
         m.insert(5567, "m-oap");
-        m.insert(7165, "doc-sertver");
+        m.insert(7165, "doc-server");
         m.insert(4142, "oidocsvc");
         m.insert(3161, "doc1lm");
         m.insert(5352, "dns-llq");
@@ -4818,7 +4820,7 @@ lazy_static! {
         m.insert(1172, "dnap");
         m.insert(5993, "cim-rs");
         m.insert(3199, "dmod-workspace");
-        m.insert(3574, "dmaf-sertver");
+        m.insert(3574, "dmaf-server");
         m.insert(4059, "dlms-cosem");
         m.insert(1177, "dkmessenger");
         m.insert(3624, "dist-upgrade");
@@ -4843,18 +4845,18 @@ lazy_static! {
         m.insert(3223, "digivote");
         m.insert(1335, "digital-notary");
         m.insert(2226, "di-drm");
-        m.insert(33333, "dgi-sertv");
+        m.insert(33333, "dgi-serv");
         m.insert(2762, "dicom-tls");
         m.insert(2761, "dicom-iscl");
         m.insert(3227, "dwnmshttp");
-        m.insert(3228, "dwmsgsertver");
+        m.insert(3228, "dwmsgserver");
         m.insert(33331, "diamondport");
         m.insert(2861, "dialpad-voice2");
         m.insert(2860, "dialpad-voice1");
         m.insert(2098, "dialog-port");
         m.insert(4301, "d-data");
         m.insert(3252, "dhe");
-        m.insert(547, "dhcpv6-sertver");
+        m.insert(547, "dhcpv6-server");
         m.insert(546, "dhcpv6-client");
         m.insert(6785, "dgpf-exchg");
         m.insert(8750, "dey-keyneg");
@@ -4865,7 +4867,7 @@ lazy_static! {
         m.insert(2763, "desktop-dna");
         m.insert(4167, "ddgn");
         m.insert(2092, "descent3");
-        m.insert(3444, "denali-sertver");
+        m.insert(3444, "denali-server");
         m.insert(8415, "dlpx-sp");
         m.insert(3714, "delos-dms");
         m.insert(1278, "dellwebadmin-1");
@@ -4907,7 +4909,7 @@ lazy_static! {
         m.insert(2297, "d2k-datamover1");
         m.insert(9388, "d2ddatatrans");
         m.insert(9387, "d2dconfig");
-        m.insert(3120, "d2000websertver");
+        m.insert(3120, "d2000webserver");
         m.insert(3297, "cytel-lm");
         m.insert(1898, "cymtec-port");
         m.insert(8442, "cybro-a-bus");
@@ -4923,7 +4925,7 @@ lazy_static! {
         m.insert(4452, "ctiprogramload");
         m.insert(9346, "ctechlicensing");
         m.insert(7022, "ctdp");
-        m.insert(3631, "cs-sertvices");
+        m.insert(3631, "cs-services");
         m.insert(3630, "cs-remote-db");
         m.insert(105, "csnet-ns");
         m.insert(3271, "csoft-prev");
@@ -4943,7 +4945,7 @@ lazy_static! {
         m.insert(41797, "crestron-ctps");
         m.insert(41796, "crestron-cips");
         m.insert(38002, "cresco-control");
-        m.insert(3364, "creativesertver");
+        m.insert(3364, "creativeserver");
         m.insert(3366, "creativepartnr");
         m.insert(2824, "cqg-netlan-1");
         m.insert(2823, "cqg-netlan");
@@ -4983,13 +4985,13 @@ lazy_static! {
         m.insert(10321, "cosir");
         m.insert(42508, "candp");
         m.insert(3822, "acp-discovery");
-        m.insert(2417, "composit-sertver");
+        m.insert(2417, "composit-server");
         m.insert(2555, "compaq-wcp");
-        m.insert(3257, "cpqrpm-sertver");
+        m.insert(3257, "cpqrpm-server");
         m.insert(3256, "cpqrpm-agent");
         m.insert(22343, "cis-secure");
         m.insert(64, "covia");
-        m.insert(7215, "PS-sertver");
+        m.insert(7215, "PS-server");
         m.insert(20003, "commtact-https");
         m.insert(4450, "camp");
         m.insert(3751, "gprs-cube");
@@ -5010,7 +5012,7 @@ lazy_static! {
         m.insert(3576, "cmc-port");
         m.insert(3575, "ccm-port");
         m.insert(2976, "cns-srv-port");
-        m.insert(7168, "cnckadsertver");
+        m.insert(7168, "cnckadserver");
         m.insert(8501, "cmtp-mgt");
         m.insert(164, "cmip-agent");
         m.insert(3384, "hp-clic");
@@ -5063,7 +5065,7 @@ lazy_static! {
         m.insert(39063, "vroa");
         m.insert(1891, "childkey-notif");
         m.insert(1892, "childkey-ctrl");
-        m.insert(3349, "chevinsertvices");
+        m.insert(3349, "chevinservices");
         m.insert(18241, "checkpoint-rtm");
         m.insert(18243, "clusterxl");
         m.insert(18242, "iclid");
@@ -5082,7 +5084,7 @@ lazy_static! {
         m.insert(4074, "cequint-cityid");
         m.insert(4140, "cedros_fds");
         m.insert(4115, "cds");
-        m.insert(3056, "cdl-sertver");
+        m.insert(3056, "cdl-server");
         m.insert(3616, "cd3o-protocol");
         m.insert(3559, "cctv-port");
         m.insert(4970, "ccss-qsm");
@@ -5160,9 +5162,9 @@ lazy_static! {
         m.insert(28589, "bosswave");
         m.insert(47808, "bacnet");
         m.insert(3966, "bflckmgr");
-        m.insert(2446, "bues_sertvice");
+        m.insert(2446, "bues_service");
         m.insert(1854, "buddy-draw");
-        m.insert(1961, "bts-appsertver");
+        m.insert(1961, "bts-appserver");
         m.insert(2444, "btpp2sectrans");
         m.insert(2277, "bluectrlproxy");
         m.insert(4175, "bccp");
@@ -5186,7 +5188,7 @@ lazy_static! {
         m.insert(7648, "cuseeme");
         m.insert(4914, "bones");
         m.insert(7687, "bolt");
-        m.insert(6501, "boks_sertvc");
+        m.insert(6501, "boks_servc");
         m.insert(9750, "board-voip");
         m.insert(3344, "bnt-manager");
         m.insert(1896, "b-novative-ls");
@@ -5215,7 +5217,7 @@ lazy_static! {
         m.insert(5068, "bitforestsrv");
         m.insert(25955, "bf-master");
         m.insert(25954, "bf-game");
-        m.insert(6946, "biosertver");
+        m.insert(6946, "bioserver");
         m.insert(3411, "biolink-auth");
         m.insert(24577, "bilobit");
         m.insert(5429, "base");
@@ -5288,10 +5290,10 @@ lazy_static! {
         m.insert(1520, "atm-zip-office");
         m.insert(11367, "atm-uhas");
         m.insert(4154, "atlinks");
-        m.insert(3617, "sharp-sertver");
+        m.insert(3617, "sharp-server");
         m.insert(20999, "athand-mmp");
         m.insert(1170, "atc-lm");
-        m.insert(1171, "atc-appsertver");
+        m.insert(1171, "atc-appserver");
         m.insert(2864, "astromed-main");
         m.insert(27876, "astrolink");
         m.insert(4485, "assyst-dr");
@@ -5303,7 +5305,7 @@ lazy_static! {
         m.insert(4404, "ds-admin");
         m.insert(4406, "ds-slp");
         m.insert(4402, "ds-clnt");
-        m.insert(4403, "ds-usert");
+        m.insert(4403, "ds-user");
         m.insert(4400, "ds-srv");
         m.insert(5727, "asgenf");
         m.insert(11489, "asgcypresstcps");
@@ -5316,12 +5318,12 @@ lazy_static! {
         m.insert(3451, "asam");
         m.insert(386, "asa");
         m.insert(8211, "aruba-papi");
-        m.insert(7166, "aruba-sertver");
+        m.insert(7166, "aruba-server");
         m.insert(3518, "artifact-msg");
         m.insert(27782, "ars-vista");
         m.insert(3176, "ars-master");
         m.insert(9292, "armtechdaemon");
-        m.insert(3174, "armi-sertver");
+        m.insert(3174, "armi-server");
         m.insert(9295, "armcenterhttps");
         m.insert(9294, "armcenterhttp");
         m.insert(3426, "arkivio");
@@ -5333,7 +5335,7 @@ lazy_static! {
         m.insert(6344, "streletz");
         m.insert(2581, "argis-te");
         m.insert(2582, "argis-ds");
-        m.insert(11321, "arena-sertver");
+        m.insert(11321, "arena-server");
         m.insert(384, "arns");
         m.insert(23546, "areaguard-neo");
         m.insert(1834, "ardusuni");
@@ -5343,10 +5345,10 @@ lazy_static! {
         m.insert(3758, "apw-registry");
         m.insert(7847, "csoauth");
         m.insert(5086, "aprigo-cs");
-        m.insert(4849, "appsertv-https");
+        m.insert(4849, "appserv-https");
         m.insert(2037, "applus");
         m.insert(1447, "apri-lm");
-        m.insert(3312, "appman-sertver");
+        m.insert(3312, "appman-server");
         m.insert(187, "aci");
         m.insert(4488, "awacs-ice");
         m.insert(2336, "appleugcontrol");
@@ -5355,7 +5357,7 @@ lazy_static! {
         m.insert(207, "at-7");
         m.insert(203, "at-3");
         m.insert(3454, "mira");
-        m.insert(10548, "sertverdocs");
+        m.insert(10548, "serverdocs");
         m.insert(4674, "appiq-mgmt");
         m.insert(38203, "agpolicy");
         m.insert(3239, "apparenet-ui");
@@ -5398,15 +5400,15 @@ lazy_static! {
         m.insert(30003, "amicon-fpsu-ra");
         m.insert(5192, "aol-2");
         m.insert(4393, "apwi-rxspooler");
-        m.insert(4392, "apwi-rxsertver");
-        m.insert(4391, "apwi-imsertver");
+        m.insert(4392, "apwi-rxserver");
+        m.insert(4391, "apwi-imserver");
         m.insert(4394, "apwi-disc");
         m.insert(1931, "amdsched");
         m.insert(5506, "amc");
         m.insert(8301, "amberon");
         m.insert(4563, "amahi-anywhere");
         m.insert(35355, "altova-lm");
-        m.insert(4011, "altsertviceboot");
+        m.insert(4011, "altserviceboot");
         m.insert(7799, "altbsdp");
         m.insert(3265, "altav-tunnel");
         m.insert(9209, "almobile-system");
@@ -5428,7 +5430,7 @@ lazy_static! {
         m.insert(17555, "ailith");
         m.insert(2786, "aic-oncrpc");
         m.insert(3316, "aicc-cmi");
-        m.insert(3021, "agrisertver");
+        m.insert(3021, "agriserver");
         m.insert(3026, "agri-gateway");
         m.insert(4878, "inst-discovery");
         m.insert(3917, "aftmux");
@@ -5445,8 +5447,8 @@ lazy_static! {
         m.insert(3760, "adtempusclient");
         m.insert(3357, "adtech-test");
         m.insert(3954, "adrep");
-        m.insert(3705, "adobesertver-5");
-        m.insert(3704, "adobesertver-4");
+        m.insert(3705, "adobeserver-5");
+        m.insert(3704, "adobeserver-4");
         m.insert(2692, "admins-lms");
         m.insert(6769, "adi-gxp-srvprt");
         m.insert(33890, "digilent-adept");
@@ -5466,7 +5468,7 @@ lazy_static! {
         m.insert(6801, "acnet");
         m.insert(4013, "acl-manager");
         m.insert(2476, "ace-svr-prop");
-        m.insert(2475, "ace-sertver");
+        m.insert(2475, "ace-server");
         m.insert(2334, "ace-client");
         m.insert(12007, "accuracer");
         m.insert(12008, "accuracer-dbms");
@@ -5512,7 +5514,7 @@ lazy_static! {
         m.insert(2133, "zymed-zpp");
         m.insert(3774, "zicom");
         m.insert(317, "zannet");
-        m.insert(3832, "xxnetsertver");
+        m.insert(3832, "xxnetserver");
         m.insert(508, "xvttp");
         m.insert(3721, "xsync");
         m.insert(1619, "xs-openstorage");
@@ -5565,7 +5567,7 @@ lazy_static! {
         m.insert(2538, "vnwk-prapi");
         m.insert(3321, "vnsstr");
         m.insert(175, "vmnet");
-        m.insert(1868, "viziblebrowsert");
+        m.insert(1868, "viziblebrowser");
         m.insert(4344, "vinainstall");
         m.insert(1853, "vids-avtp");
         m.insert(3058, "videobeans");
@@ -5638,7 +5640,7 @@ lazy_static! {
         m.insert(1838, "talnet");
         m.insert(4014, "taiclock");
         m.insert(1274, "t1distproc");
-        m.insert(2647, "syncsertver");
+        m.insert(2647, "syncserver");
         m.insert(4106, "synchronite");
         m.insert(6102, "synchronet-upd");
         m.insert(4548, "synchromesh");
@@ -5673,7 +5675,7 @@ lazy_static! {
         m.insert(2636, "solve");
         m.insert(1621, "softdataphone");
         m.insert(3379, "socorfs");
-        m.insert(2533, "sniffersertver");
+        m.insert(2533, "snifferserver");
         m.insert(2892, "snifferdata");
         m.insert(2452, "snifferclient");
         m.insert(1684, "snaresecure");
@@ -5702,7 +5704,7 @@ lazy_static! {
         m.insert(2676, "simslink");
         m.insert(3687, "simple-push");
         m.insert(2756, "simplement-tie");
-        m.insert(1599, "simbasertvices");
+        m.insert(1599, "simbaservices");
         m.insert(2832, "silkp4");
         m.insert(2831, "silkp3");
         m.insert(2830, "silkp2");
@@ -5716,13 +5718,13 @@ lazy_static! {
         m.insert(153, "sgmp");
         m.insert(3326, "sftu");
         m.insert(1714, "sesi-lm");
-        m.insert(3172, "sertverview-rm");
-        m.insert(3173, "sertverview-icc");
-        m.insert(3171, "sertverview-gf");
-        m.insert(3170, "sertverview-asn");
-        m.insert(3169, "sertverview-as");
-        m.insert(2235, "sertcomm-wlink");
-        m.insert(6108, "sertcomm-scadmin");
+        m.insert(3172, "serverview-rm");
+        m.insert(3173, "serverview-icc");
+        m.insert(3171, "serverview-gf");
+        m.insert(3170, "serverview-asn");
+        m.insert(3169, "serverview-as");
+        m.insert(2235, "sercomm-wlink");
+        m.insert(6108, "sercomm-scadmin");
         m.insert(169, "send");
         m.insert(5399, "securitychase");
         m.insert(2471, "seaodbc");
@@ -5766,7 +5768,7 @@ lazy_static! {
         m.insert(2321, "rdlap");
         m.insert(3467, "rcst");
         m.insert(9753, "rasadv");
-        m.insert(1530, "rap-sertvice");
+        m.insert(1530, "rap-service");
         m.insert(2872, "radix");
         m.insert(1595, "radio");
         m.insert(2900, "quicksuite");
@@ -5784,13 +5786,13 @@ lazy_static! {
         m.insert(3009, "pxc-ntfy");
         m.insert(2434, "pxc-epmap");
         m.insert(2694, "pwrsevent");
-        m.insert(2351, "psrsertver");
-        m.insert(2353, "pspsertver");
-        m.insert(2354, "psprsertver");
+        m.insert(2351, "psrserver");
+        m.insert(2353, "pspserver");
+        m.insert(2354, "psprserver");
         m.insert(1788, "psmond");
-        m.insert(2352, "pslsertver");
-        m.insert(3662, "psertver");
-        m.insert(2355, "psdbsertver");
+        m.insert(2352, "pslserver");
+        m.insert(3662, "pserver");
+        m.insert(2355, "psdbserver");
         m.insert(2091, "prp");
         m.insert(1732, "proxim");
         m.insert(8183, "proremote");
@@ -5873,10 +5875,10 @@ lazy_static! {
         m.insert(2839, "nmsigport");
         m.insert(2503, "nms-dpnss");
         m.insert(25903, "niprobe");
-        m.insert(25901, "niobsertver");
+        m.insert(25901, "niobserver");
         m.insert(25902, "nilinkanalyst");
         m.insert(2556, "nicetec-nmsvc");
-        m.insert(2672, "nhsertver");
+        m.insert(2672, "nhserver");
         m.insert(1690, "ng-umds");
         m.insert(2360, "nexstorindltd");
         m.insert(2671, "newlixreg");
@@ -5928,7 +5930,7 @@ lazy_static! {
         m.insert(19398, "mtrgtrans");
         m.insert(2469, "mti-tcs-comm");
         m.insert(2460, "ms-theater");
-        m.insert(1477, "ms-sna-sertver");
+        m.insert(1477, "ms-sna-server");
         m.insert(1478, "ms-sna-base");
         m.insert(5720, "ms-licensing");
         m.insert(3535, "ms-la");
@@ -5963,7 +5965,7 @@ lazy_static! {
         m.insert(2511, "metastorm");
         m.insert(1897, "metaagent");
         m.insert(6070, "messageasap");
-        m.insert(2118, "mentasertver");
+        m.insert(2118, "mentaserver");
         m.insert(2117, "mentaclient");
         m.insert(1231, "menandmice-lpm");
         m.insert(24003, "med-supp");
@@ -5976,7 +5978,7 @@ lazy_static! {
         m.insert(24005, "med-ci");
         m.insert(5418, "mcntp");
         m.insert(2698, "mck-ivpip");
-        m.insert(8763, "mc-appsertver");
+        m.insert(8763, "mc-appserver");
         m.insert(1820, "mcagent");
         m.insert(1899, "mc2studios");
         m.insert(2587, "masc");
@@ -6022,7 +6024,7 @@ lazy_static! {
         m.insert(2656, "kana");
         m.insert(2213, "kali");
         m.insert(2822, "ka0wuc");
-        m.insert(1289, "jwalksertver");
+        m.insert(1289, "jwalkserver");
         m.insert(3471, "jt400-ssl");
         m.insert(3470, "jt400");
         m.insert(3042, "journee");
@@ -6032,12 +6034,12 @@ lazy_static! {
         m.insert(1567, "jlicelmd");
         m.insert(2808, "j-lan-p");
         m.insert(1706, "jetform");
-        m.insert(2406, "jedisertver");
+        m.insert(2406, "jediserver");
         m.insert(2508, "jdatastore");
         m.insert(2506, "jbroker");
         m.insert(1623, "jaleosnd");
         m.insert(13160, "i-zipqd");
-        m.insert(2166, "iwsertver");
+        m.insert(2166, "iwserver");
         m.insert(2866, "iwlistener");
         m.insert(2982, "iwb-whiteboard");
         m.insert(1275, "ivcollector");
@@ -6064,7 +6066,7 @@ lazy_static! {
         m.insert(1907, "intrastar");
         m.insert(3202, "intraintra");
         m.insert(3548, "interworld");
-        m.insert(3060, "intersertver");
+        m.insert(3060, "interserver");
         m.insert(2652, "interpathpanel");
         m.insert(2633, "interintelli");
         m.insert(16991, "intel-rci-mp");
@@ -6108,7 +6110,7 @@ lazy_static! {
         m.insert(25009, "icl-twobase10");
         m.insert(6850, "iccrushmore");
         m.insert(1344, "icap");
-        m.insert(1604, "icabrowsert");
+        m.insert(1604, "icabrowser");
         m.insert(8733, "ibus");
         m.insert(2572, "ibp");
         m.insert(1260, "ibm-ssd");
@@ -6156,7 +6158,7 @@ lazy_static! {
         m.insert(41, "graphics");
         m.insert(2519, "globmsgsvc");
         m.insert(6109, "globecast-id");
-        m.insert(1774, "global-dtsertv");
+        m.insert(1774, "global-dtserv");
         m.insert(2833, "glishd");
         m.insert(3862, "giga-pocket");
         m.insert(3381, "geneous");
@@ -6175,7 +6177,7 @@ lazy_static! {
         m.insert(1746, "ftrapid-1");
         m.insert(5408, "foresyte-sec");
         m.insert(5407, "foresyte-clear");
-        m.insert(2359, "flukesertver");
+        m.insert(2359, "flukeserver");
         m.insert(24677, "flashfiler");
         m.insert(1874, "fjswapsnp");
         m.insert(2946, "fjsvmpor");
@@ -6201,8 +6203,8 @@ lazy_static! {
         m.insert(3293, "fg-fps");
         m.insert(5503, "fcp-srvr-inst2");
         m.insert(5504, "fcp-cics-gw1");
-        m.insert(5746, "fcopys-sertver");
-        m.insert(5745, "fcopy-sertver");
+        m.insert(5746, "fcopys-server");
+        m.insert(5745, "fcopy-server");
         m.insert(2344, "fcmsys");
         m.insert(7437, "faximum");
         m.insert(3353, "fatpipe");
@@ -6256,11 +6258,11 @@ lazy_static! {
         m.insert(1325, "dx-instrument");
         m.insert(2445, "dtn1");
         m.insert(1265, "dssiapi");
-        m.insert(3053, "dsom-sertver");
+        m.insert(3053, "dsom-server");
         m.insert(1292, "dsdn");
         m.insert(1878, "drmsmc");
         m.insert(4098, "drmsfsd");
-        m.insert(1780, "dpkeysertv");
+        m.insert(1780, "dpkeyserv");
         m.insert(1795, "dpi-proxy");
         m.insert(4099, "dpcp");
         m.insert(1821, "donnyworld");
@@ -6285,12 +6287,12 @@ lazy_static! {
         m.insert(3046, "di-ase");
         m.insert(1945, "dialogic-elmd");
         m.insert(6072, "diagnose-proc");
-        m.insert(2960, "dfoxsertver");
+        m.insert(2960, "dfoxserver");
         m.insert(5426, "devbasic");
         m.insert(2753, "de-spot");
         m.insert(3298, "deskview");
         m.insert(1702, "deskshare");
-        m.insert(1256, "de-sertver");
+        m.insert(1256, "de-server");
         m.insert(1254, "de-noc");
         m.insert(1266, "dellpwrappks");
         m.insert(2562, "delibo");
@@ -6310,7 +6312,7 @@ lazy_static! {
         m.insert(2870, "daishi");
         m.insert(3458, "d3winosfi");
         m.insert(5420, "cylink-c");
-        m.insert(772, "cyclesertv2");
+        m.insert(772, "cycleserv2");
         m.insert(3645, "cyc");
         m.insert(551, "cybercash");
         m.insert(1686, "cvmon");
@@ -6404,7 +6406,7 @@ lazy_static! {
         m.insert(482, "bgs-nsi");
         m.insert(2414, "beeyond");
         m.insert(2852, "bears-01");
-        m.insert(1951, "bcs-lmsertver");
+        m.insert(1951, "bcs-lmserver");
         m.insert(1704, "bcs-broker");
         m.insert(3327, "bbars");
         m.insert(573, "banyan-vip");
@@ -6413,7 +6415,7 @@ lazy_static! {
         m.insert(2131, "avantageb2b");
         m.insert(2772, "auris");
         m.insert(3643, "audiojuggler");
-        m.insert(1749, "aspen-sertvices");
+        m.insert(1749, "aspen-services");
         m.insert(5042, "asnaacceler8db");
         m.insert(1913, "armadp");
         m.insert(2624, "aria");
@@ -6465,21 +6467,9 @@ lazy_static! {
         m.insert(1742, "3Com-nsd");
         m
     };
-
-    static ref PORTS_KNOWN: Vec<u16> = {
-        let mut v = Vec::with_capacity(PORTS_NAME.len());
-        for item in PORTS_NAME.iter() {
-            v.push(*item.0);
-        }
-        v.sort();
-        v
-    };
-}
-
-pub fn get_port_name(port: u16) -> Option<&'static str> {
-    PORTS_NAME.get(&port).copied()
 }
 
-pub fn get_ports_known() -> &'static [u16] {
-    &PORTS_KNOWN
+/// The embedded TCP ports and names, most frequently open first.
+pub(super) fn embedded() -> impl Iterator<Item = (u16, &'static str)> {
+    PORTS_NAME.iter().map(|(port, name)| (*port, *name))
 }
@@ -0,0 +1,153 @@
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use super::ports;
+use crate::error::{Result, ScanError};
+
+/// A port's entry in the service database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Service {
+    pub name: String,
+    /// Ratio of scans that found the port open, `None` if not recorded.
+    pub frequency: Option<f64>,
+    pub comment: Option<String>,
+}
+
+/// Services by protocol name (`tcp`, `udp`, `sctp`) and port.
+#[derive(Debug, Default)]
+pub struct Services {
+    services: HashMap<String, HashMap<u16, Service>>,
+    /// Each protocol's ports, most frequently open first.
+    top: HashMap<String, Vec<u16>>,
+    /// Each protocol's ports in numerical order.
+    known: HashMap<String, Vec<u16>>,
+}
+
+impl Services {
+    /// The table built into the binary. It only names TCP ports.
+    pub fn embedded() -> Self {
+        Services::from_entries(ports::embedded().map(|(port, name)| {
+            (
+                "tcp".to_string(),
+                port,
+                Service {
+                    name: name.to_string(),
+                    frequency: None,
+                    comment: None,
+                },
+            )
+        }))
+    }
+
+    /// Reads an nmap-services file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| content.parse())
+            .map_err(|e| {
+                ScanError::Config(format!("can not load services {}: {}", path.display(), e))
+            })
+    }
+
+    /// Keeps the first entry for a port, entries without a frequency rank
+    /// after every one with it and otherwise keep their order.
+    fn from_entries(entries: impl Iterator<Item = (String, u16, Service)>) -> Self {
+        let mut db = Services::default();
+
+        for (protocol, port, service) in entries {
+            let services = db.services.entry(protocol.clone()).or_default();
+            if services.contains_key(&port) {
+                continue;
+            }
+            services.insert(port, service);
+            db.top.entry(protocol).or_default().push(port);
+        }
+
+        for (protocol, top) in db.top.iter_mut() {
+            let services = &db.services[protocol];
+            let frequency = |port: &u16| services[port].frequency.unwrap_or(-1.0);
+            top.sort_by(|a, b| frequency(b).total_cmp(&frequency(a)));
+
+            let mut known = top.clone();
+            known.sort();
+            db.known.insert(protocol.clone(), known);
+        }
+
+        db
+    }
+
+    pub fn get(&self, protocol: &str, port: u16) -> Option<&Service> {
+        self.services.get(protocol)?.get(&port)
+    }
+
+    /// The name of a port, `unknown` if there is none.
+    pub fn name(&self, protocol: &str, port: u16) -> &str {
+        self.get(protocol, port)
+            .map_or("unknown", |x| x.name.as_str())
+    }
+
+    /// The `n` most frequently open ports, or all of them if there are
+    /// fewer.
+    pub fn top(&self, protocol: &str, n: usize) -> &[u16] {
+        let top = self.top.get(protocol).map_or(&[][..], Vec::as_slice);
+        &top[..n.min(top.len())]
+    }
+
+    pub fn known(&self, protocol: &str) -> &[u16] {
+        self.known.get(protocol).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl FromStr for Services {
+    type Err = String;
+
+    /// nmap-services lines: `name port/protocol [frequency] [# comment]`,
+    /// blank lines and lines holding only a comment are skipped.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let (line, comment) = match line.split_once('#') {
+                Some((line, comment)) => (line, Some(comment.trim()).filter(|x| !x.is_empty())),
+                None => (line, None),
+            };
+
+            let mut fields = line.split_whitespace();
+            let Some(name) = fields.next() else {
+                continue;
+            };
+
+            let invalid =
+                |what: &str, field: &str| format!("line {}: invalid {}: {}", i + 1, what, field);
+
+            let field = fields.next().unwrap_or_default();
+            let (port, protocol) = field
+                .split_once('/')
+                .and_then(|(port, protocol)| Some((port.parse::<u16>().ok()?, protocol)))
+                .ok_or_else(|| invalid("port/protocol", field))?;
+
+            let frequency = match fields.next() {
+                Some(field) => Some(
+                    field
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|x| (0.0..=1.0).contains(x))
+                        .ok_or_else(|| invalid("frequency", field))?,
+                ),
+                None => None,
+            };
+
+            entries.push((
+                protocol.to_ascii_lowercase(),
+                port,
+                Service {
+                    name: name.to_string(),
+                    frequency,
+                    comment: comment.map(str::to_string),
+                },
+            ));
+        }
+
+        Ok(Services::from_entries(entries.into_iter()))
+    }
+}
@@ -1,160 +1,181 @@
 use colored::Colorize;
 
-use crate::config::get_port_name;
+use crate::{
+    config::Services, error::Result, toml_parser::ShowRule, PortResult, PortState, Protocol,
+    ScanResult,
+};
 use std::{
     collections::BTreeMap,
     fs::{self, File},
     io::Write,
-    net::{Ipv4Addr, SocketAddrV4},
+    net::IpAddr,
     path::Path,
 };
 
-struct TargetStates {
-    open: Vec<(u16, &'static str)>,
-    closed: Vec<(u16, &'static str)>,
-    filtered: Vec<(u16, &'static str)>,
+/// Port, service name and the number of probes it took to classify.
+type PortEntry<'a> = (u16, &'a str, u8);
+
+#[derive(Default)]
+struct TargetStates<'a> {
+    open: Vec<PortEntry<'a>>,
+    closed: Vec<PortEntry<'a>>,
+    filtered: Vec<PortEntry<'a>>,
+    unfiltered: Vec<PortEntry<'a>>,
+    open_filtered: Vec<PortEntry<'a>>,
 }
 
-impl TargetStates {
-    fn new() -> Self {
-        Self {
-            open: Vec::new(),
-            closed: Vec::new(),
-            filtered: Vec::new(),
+impl<'a> TargetStates<'a> {
+    fn get_mut(&mut self, state: PortState) -> &mut Vec<PortEntry<'a>> {
+        match state {
+            PortState::Open => &mut self.open,
+            PortState::Closed => &mut self.closed,
+            PortState::Filtered => &mut self.filtered,
+            PortState::Unfiltered => &mut self.unfiltered,
+            PortState::OpenFiltered => &mut self.open_filtered,
         }
     }
 }
 
+/// Results of one host, split by the protocol they were probed with.
+type TargetInfo<'a> = BTreeMap<Protocol, TargetStates<'a>>;
+
+/// Writes `result` as toml, naming ports after `services`.
 pub fn display(
-    open_ports: Vec<SocketAddrV4>,
-    closed_ports: Vec<SocketAddrV4>,
-    filtered_ports: Vec<SocketAddrV4>,
-    show_open: bool,
-    show_closed: bool,
-    show_filtered: bool,
+    result: &ScanResult,
+    show: &ShowRule,
+    services: &Services,
     output_path: String,
-) {
-    let tree = get_info_tree(&open_ports, &closed_ports, &filtered_ports);
+) -> Result<()> {
+    let tree = get_info_tree(result, services);
 
-    let mut file = create_file(&output_path).unwrap_or_else(|e| {
+    let mut file = create_file(&output_path).or_else(|e| {
         eprintln!("{} {}", "OUTPUT FAILED: ".red().bold(), e);
         println!(
             "{}",
             "REDIRECTING OUTPUT PATH TO CURRENT DIR".yellow().bold()
         );
-        File::create("output.toml").unwrap()
-    });
+        File::create("output.toml")
+    })?;
 
-    writeln!(file, "[summary]").unwrap();
+    writeln!(file, "[summary]")?;
     writeln!(
         file,
         "total = {}",
-        open_ports.len() + closed_ports.len() + filtered_ports.len()
-    )
-    .unwrap();
-    writeln!(file, "open = {}", open_ports.len()).unwrap();
-    writeln!(file, "closed = {}", closed_ports.len()).unwrap();
-    writeln!(file, "filtered = {}", filtered_ports.len()).unwrap();
-    writeln!(file).unwrap();
-
-    for (target_ip, target_states) in tree {
-        writeln!(file, "[[target]]").unwrap();
-        writeln!(file, "ip = \"{}\"", target_ip).unwrap();
-        writeln!(file).unwrap();
-
-        if show_open {
-            writeln!(file, "open = [").unwrap();
-
-            target_states.open.iter().for_each(|(port, name)| {
-                writeln!(file, "    {{ port = {}, name = \"{}\" }},", port, name).unwrap()
-            });
-
-            writeln!(file, "]").unwrap();
+        result.open.len()
+            + result.closed.len()
+            + result.filtered.len()
+            + result.unfiltered.len()
+            + result.open_filtered.len()
+    )?;
+    writeln!(file, "open = {}", result.open.len())?;
+    writeln!(file, "closed = {}", result.closed.len())?;
+    writeln!(file, "filtered = {}", result.filtered.len())?;
+    writeln!(file, "unfiltered = {}", result.unfiltered.len())?;
+    writeln!(file, "open_filtered = {}", result.open_filtered.len())?;
+    writeln!(file, "hosts_up = {}", result.reachable.len())?;
+    writeln!(file)?;
+
+    for (target_ip, target_info) in tree {
+        writeln!(file, "[[target]]")?;
+        writeln!(file, "ip = \"{}\"", target_ip)?;
+        if let Some(reason) = result.reasons.get(&target_ip) {
+            writeln!(file, "reason = \"{}\"", reason)?;
         }
-
-        writeln!(file).unwrap();
-
-        if show_closed {
-            writeln!(file, "closed = [").unwrap();
-
-            target_states.closed.iter().for_each(|(port, name)| {
-                writeln!(file, "    {{ port = {}, name = \"{}\" }},", port, name).unwrap()
-            });
-
-            writeln!(file, "]").unwrap();
+        if let Some(mac) = result.macs.get(&target_ip) {
+            writeln!(file, "mac = \"{}\"", mac)?;
         }
-
-        if show_filtered {
-            writeln!(file, "filtered = [").unwrap();
-
-            target_states.filtered.iter().for_each(|(port, name)| {
-                writeln!(file, "    {{ port = {}, name = \"{}\" }},", port, name).unwrap()
-            });
-
-            writeln!(file, "]").unwrap();
+        writeln!(file)?;
+
+        for (protocol, target_states) in target_info {
+            writeln!(file, "[target.{}]", protocol)?;
+
+            let sections = [
+                (show.open, "open", &target_states.open),
+                (show.closed, "closed", &target_states.closed),
+                (show.filtered, "filtered", &target_states.filtered),
+                (show.unfiltered, "unfiltered", &target_states.unfiltered),
+                (
+                    show.open_filtered,
+                    "open_filtered",
+                    &target_states.open_filtered,
+                ),
+            ];
+
+            for (shown, key, entries) in sections {
+                if !shown {
+                    continue;
+                }
+
+                writeln!(file, "{} = [", key)?;
+
+                for entry in entries.iter() {
+                    write_port_entry(&mut file, entry)?;
+                }
+
+                writeln!(file, "]")?;
+            }
+
+            writeln!(file)?;
         }
-
-        writeln!(file).unwrap();
     }
 
     println!("{}: {}", "OUTPUT FILE PATH".green().bold(), output_path);
+
+    Ok(())
+}
+
+fn write_port_entry(file: &mut File, (port, name, attempts): &PortEntry) -> Result<()> {
+    writeln!(
+        file,
+        "    {{ port = {}, name = \"{}\", attempts = {} }},",
+        port, name, attempts
+    )?;
+    Ok(())
 }
 
-fn get_info_tree(
-    open_ports: &[SocketAddrV4],
-    closed_ports: &[SocketAddrV4],
-    filtered_ports: &[SocketAddrV4],
-) -> BTreeMap<Ipv4Addr, TargetStates> {
-    let mut tree = BTreeMap::<Ipv4Addr, TargetStates>::new();
-
-    open_ports.iter().for_each(|x| {
-        tree.entry(*x.ip())
-            .and_modify(|v| {
-                v.open
-                    .push((x.port(), get_port_name(x.port()).unwrap_or("unKnown")))
-            })
-            .or_insert_with(|| {
-                let mut target_states = TargetStates::new();
-                target_states
-                    .open
-                    .push((x.port(), get_port_name(x.port()).unwrap_or("unknown")));
-                target_states
-            });
-    });
-
-    closed_ports.iter().for_each(|x| {
-        tree.entry(*x.ip())
-            .and_modify(|v| {
-                v.closed
-                    .push((x.port(), get_port_name(x.port()).unwrap_or("unKnown")))
-            })
-            .or_insert_with(|| {
-                let mut target_states = TargetStates::new();
-                target_states
-                    .closed
-                    .push((x.port(), get_port_name(x.port()).unwrap_or("unknown")));
-                target_states
-            });
-    });
-
-    filtered_ports.iter().for_each(|x| {
-        tree.entry(*x.ip())
-            .and_modify(|v| {
-                v.filtered
-                    .push((x.port(), get_port_name(x.port()).unwrap_or("unKnown")))
-            })
-            .or_insert_with(|| {
-                let mut target_states = TargetStates::new();
-                target_states
-                    .filtered
-                    .push((x.port(), get_port_name(x.port()).unwrap_or("unknown")));
-                target_states
-            });
-    });
+/// Every host that is up, with the results of its ports if they were
+/// scanned.
+fn get_info_tree<'a>(
+    result: &ScanResult,
+    services: &'a Services,
+) -> BTreeMap<IpAddr, TargetInfo<'a>> {
+    let mut tree: BTreeMap<IpAddr, TargetInfo> = result
+        .reachable
+        .iter()
+        .map(|ip| (*ip, TargetInfo::new()))
+        .collect();
+
+    let groups = [
+        (PortState::Open, &result.open),
+        (PortState::Closed, &result.closed),
+        (PortState::Filtered, &result.filtered),
+        (PortState::Unfiltered, &result.unfiltered),
+        (PortState::OpenFiltered, &result.open_filtered),
+    ];
+
+    for (state, ports) in groups {
+        ports.iter().for_each(|x| {
+            tree.entry(x.socket.ip())
+                .or_default()
+                .entry(x.protocol)
+                .or_default()
+                .get_mut(state)
+                .push(get_port_entry(x, services))
+        });
+    }
 
     tree
 }
 
+fn get_port_entry<'a>(result: &PortResult, services: &'a Services) -> PortEntry<'a> {
+    let port = result.socket.port();
+    (
+        port,
+        services.name(&result.protocol.to_string(), port),
+        result.attempts,
+    )
+}
+
 fn create_file(output_path: &str) -> std::io::Result<File> {
     if let Some(parent_path) = Path::new(&output_path).parent() {
         if !parent_path.exists() {
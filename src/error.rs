@@ -0,0 +1,62 @@
+use std::{fmt, io};
+
+pub type Result<T> = std::result::Result<T, ScanError>;
+
+/// Everything that can stop a scan before it produces results.
+///
+/// Each variant maps to its own process exit code so that callers can tell
+/// a broken profile apart from missing raw-socket rights.
+#[derive(Debug)]
+pub enum ScanError {
+    /// The profile is missing a key or holds a value of the wrong shape.
+    Config(String),
+    /// The requested network interface does not exist or is unusable.
+    Interface(String),
+    /// Opening a raw datalink channel was refused by the OS.
+    Permission(String),
+    /// Any other I/O failure while sending, receiving or writing output.
+    Io(io::Error),
+}
+
+impl ScanError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ScanError::Config(_) => 2,
+            ScanError::Interface(_) => 3,
+            ScanError::Permission(_) => 4,
+            ScanError::Io(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::Config(msg) => write!(f, "config error: {}", msg),
+            ScanError::Interface(msg) => write!(f, "interface error: {}", msg),
+            ScanError::Permission(msg) => write!(f, "permission denied: {}", msg),
+            ScanError::Io(e) => write!(f, "io error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScanError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ScanError {
+    fn from(e: io::Error) -> Self {
+        ScanError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ScanError {
+    fn from(e: toml::de::Error) -> Self {
+        ScanError::Config(e.to_string())
+    }
+}
@@ -1,45 +1,199 @@
-mod packet;
+pub(crate) mod packet;
 
 use indicatif::{ProgressBar, ProgressStyle, WeakProgressBar};
 use pnet::{
-    datalink::{self, Channel, NetworkInterface},
     packet::{
-        ethernet::EthernetPacket,
+        arp::ArpOperations,
         icmp::{IcmpPacket, IcmpTypes},
+        icmpv6::{Icmpv6Packet, Icmpv6Types},
         ip::IpNextHeaderProtocols,
-        ipv4::Ipv4Packet,
-        Packet,
+        tcp::{TcpFlags, TcpPacket},
+        udp::UdpPacket,
     },
     util::MacAddr,
 };
 
 use colored::*;
+use rand::Rng;
 use std::{
-    net::{IpAddr, Ipv4Addr},
-    sync::atomic::{AtomicBool, Ordering},
+    collections::HashMap,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    arp,
+    error::Result,
+    interface::{self, Link},
+    neighbor,
+    rate_limiter::RateLimiter,
+    scanner::{self, packet::Datagram},
+    timing::RttTable,
+    transport::{Channel, Transport},
 };
 
-static DONE: AtomicBool = AtomicBool::new(false);
+const RECV_TIMEOUT: Duration = Duration::from_millis(10);
+
+const WAIT_TICK: Duration = Duration::from_millis(5);
+
+/// Type, code, checksum and the unused word before the quoted packet.
+const ICMP_HEADER_LEN: usize = 8;
+
+/// Hosts asked whether they are up and still waiting for an answer, and
+/// the round trips measured from the answers that came back.
+struct State {
+    sent: HashMap<IpAddr, Instant>,
+    rtt: RttTable,
+}
+
+type Shared = Arc<Mutex<State>>;
+
+/// Which probes hosts behind the gateway are sent to tell whether they
+/// are up. Hosts on our own subnet are always asked with ARP or neighbor
+/// discovery instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discovery {
+    /// ICMP or ICMPv6 echo request.
+    pub echo: bool,
+    /// Ports a TCP SYN is sent to, a SYN-ACK or RST means up.
+    pub syn_ports: Vec<u16>,
+    /// Ports a TCP ACK is sent to, a RST means up.
+    pub ack_ports: Vec<u16>,
+    /// Ports an empty UDP datagram is sent to, best closed ones so a port
+    /// unreachable comes back.
+    pub udp_ports: Vec<u16>,
+}
+
+impl Default for Discovery {
+    fn default() -> Self {
+        Discovery {
+            echo: true,
+            syn_ports: Vec::new(),
+            ack_ports: Vec::new(),
+            udp_ports: Vec::new(),
+        }
+    }
+}
+
+impl Discovery {
+    /// Whether a host behind the gateway is sent no probe at all.
+    pub fn is_empty(&self) -> bool {
+        !self.echo
+            && self.syn_ports.is_empty()
+            && self.ack_ports.is_empty()
+            && self.udp_ports.is_empty()
+    }
+
+    /// The probes for a host behind the gateway.
+    fn probes(&self) -> Vec<Probe> {
+        let echo = self.echo.then_some(Probe::Echo);
+        let syn = self.syn_ports.iter().map(|x| Probe::Syn(*x));
+        let ack = self.ack_ports.iter().map(|x| Probe::Ack(*x));
+        let udp = self.udp_ports.iter().map(|x| Probe::Udp(*x));
+
+        echo.into_iter().chain(syn).chain(ack).chain(udp).collect()
+    }
+}
 
+/// What a host answered that made us call it up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Reason {
+    EchoReply,
+    ArpResponse,
+    NeighborAdvert,
+    SynAck,
+    Reset,
+    UdpResponse,
+    /// ICMP destination unreachable, sent by the host itself.
+    Unreachable,
+    /// Discovery was skipped, every host counts as up.
+    UserSet,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Reason::EchoReply => "echo-reply",
+            Reason::ArpResponse => "arp-response",
+            Reason::NeighborAdvert => "nd-response",
+            Reason::SynAck => "syn-ack",
+            Reason::Reset => "reset",
+            Reason::UdpResponse => "udp-response",
+            Reason::Unreachable => "unreachable",
+            Reason::UserSet => "user-set",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A host that is up, how we know, and its MAC when it is a neighbor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Host {
+    pub ip: IpAddr,
+    pub reason: Reason,
+    pub mac: Option<MacAddr>,
+}
+
+/// How a host is asked whether it is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Probe {
+    /// ICMP or ICMPv6 echo request through the gateway.
+    Echo,
+    /// ARP request, for IPv4 hosts on our own subnet.
+    Arp,
+    /// IPv6 neighbor solicitation, for hosts on our own prefix.
+    NeighborSolicit,
+    Syn(u16),
+    Ack(u16),
+    Udp(u16),
+}
+
+/// Asks every host whether it is up with the probes `discovery` picks and
+/// returns the ones that answered, along with `rtt` updated from the
+/// answers. Hosts on one of the interface's subnets are found through ARP
+/// or neighbor discovery instead, which firewalls blocking echo don't get
+/// in the way of.
 pub fn detect(
-    interface_ip: Ipv4Addr,
-    gateway_mac: MacAddr,
-    dest_ips: Vec<Ipv4Addr>,
-) -> Vec<Ipv4Addr> {
-    println!("{} {}", "😁", "START ICMP DETECTING: ".yellow().bold());
+    transport: Arc<dyn Transport>,
+    gateway_mac: Option<MacAddr>,
+    discovery: &Discovery,
+    dest_ips: Vec<IpAddr>,
+    rtt: RttTable,
+    limiter: Arc<RateLimiter>,
+) -> Result<(Vec<Host>, RttTable)> {
+    println!("😁 {}", "START HOST DISCOVERY: ".yellow().bold());
+
+    let link = Link::new(transport.interface(), gateway_mac)?;
+    // answers to our TCP and UDP probes come back to this port
+    let src_port = rand::thread_rng().gen_range(20000..=65535);
 
-    let interface = datalink::interfaces()
+    let remote_probes = discovery.probes();
+    let probes: Vec<(IpAddr, Vec<Probe>)> = dest_ips
         .into_iter()
-        .find(|x| x.ips.first().unwrap().ip() == IpAddr::V4(interface_ip))
-        .unwrap();
+        .map(|ip| match ip {
+            _ if !interface::is_on_link(transport.interface(), ip) => (ip, remote_probes.clone()),
+            IpAddr::V4(_) => (ip, vec![Probe::Arp]),
+            IpAddr::V6(_) => (ip, vec![Probe::NeighborSolicit]),
+        })
+        .collect();
 
-    let interface_clone = interface.clone();
-    let gateway_mac_clone = gateway_mac.clone();
-    let dest_ip_clone = dest_ips.clone();
+    let rx_channel = transport.open()?;
+    let tx_channel = transport.open()?;
 
-    let pb = ProgressBar::new(dest_ips.len() as u64);
+    let state: Shared = Arc::new(Mutex::new(State {
+        sent: HashMap::new(),
+        rtt,
+    }));
+    let rx_state = state.clone();
+    let tx_state = state.clone();
+
+    let pb = ProgressBar::new(probes.len() as u64);
     pb.set_message("DETECTING");
     pb.set_style(
         ProgressStyle::with_template(
@@ -51,93 +205,255 @@ pub fn detect(
     let rx_pb = pb.downgrade();
     let tx_pb = pb.downgrade();
 
-    let rx_thread = thread::spawn(move || receive_and_filter(interface, dest_ips, rx_pb));
+    let done = Arc::new(AtomicBool::new(false));
+    let rx_done = done.clone();
+
+    let rx_link = link.clone();
+    let rx_thread = thread::spawn(move || {
+        receive_and_filter(rx_channel, rx_link, src_port, rx_state, rx_done, rx_pb)
+    });
 
     let tx_thread = thread::spawn(move || {
-        send(interface_clone, gateway_mac_clone, dest_ip_clone, tx_pb);
+        let res = send(tx_channel, link, src_port, probes, tx_state, limiter, tx_pb);
+        done.store(true, Ordering::SeqCst);
+        res
     });
 
-    let reachable_ips = rx_thread.join().unwrap();
-    let _ = tx_thread.join().unwrap();
+    let send_res = tx_thread.join().unwrap();
+    let hosts = rx_thread.join().unwrap()?;
+    send_res?;
 
     pb.finish_with_message("😁 DETECTING DONE ");
 
-    reachable_ips
+    let rtt = state.lock().unwrap().rtt.clone();
+
+    Ok((hosts, rtt))
 }
 
+/// Sends every probe of a host back to back, then waits until every
+/// silent host has had its timeout's worth of time to answer.
 fn send(
-    interface: NetworkInterface,
-    gateway_mac: MacAddr,
-    target_dests: Vec<Ipv4Addr>,
+    mut tx: Box<dyn Channel>,
+    link: Link,
+    src_port: u16,
+    probes: Vec<(IpAddr, Vec<Probe>)>,
+    state: Shared,
+    limiter: Arc<RateLimiter>,
     pb: WeakProgressBar,
-) {
-    let (mut tx, _) = match datalink::channel(&interface, Default::default()) {
-        Ok(Channel::Ethernet(tx, rx)) => (tx, rx),
-        Ok(_) => panic!("Unknown channel type!"),
-        Err(e) => panic!("Error happened: {}", e),
-    };
+) -> Result<()> {
+    for (dest_ip, host_probes) in probes {
+        let src_ip = link.source(dest_ip)?;
 
-    let IpAddr::V4(src_ip) = interface.ips.first().unwrap().ip() else {
-        panic!();
-    };
-
-    let interface_mac = interface.mac.unwrap();
+        // the clock runs from the first probe, whichever one is answered
+        state.lock().unwrap().sent.insert(dest_ip, Instant::now());
 
-    for dest_ip in target_dests {
-        let packet_icmp = packet::build(interface_mac, src_ip, dest_ip, gateway_mac);
+        for probe in host_probes {
+            limiter.acquire();
+            tx.send(&build_probe(&link, src_ip, src_port, dest_ip, probe)?)?;
+        }
 
-        tx.send_to(&packet_icmp, None).unwrap().unwrap();
         pb.upgrade().unwrap().inc(1);
 
         thread::sleep(Duration::from_micros(1));
     }
+    loop {
+        let waiting = {
+            let state = state.lock().unwrap();
+            state
+                .sent
+                .iter()
+                .any(|(ip, sent_at)| sent_at.elapsed() < state.rtt.timeout(*ip))
+        };
 
-    thread::sleep(Duration::from_millis(100));
+        if !waiting {
+            break;
+        }
 
-    DONE.store(true, Ordering::SeqCst);
+        thread::sleep(WAIT_TICK);
+    }
+
+    Ok(())
 }
 
-fn receive_and_filter(
-    interface: NetworkInterface,
-    target_dests: Vec<Ipv4Addr>,
-    pb: WeakProgressBar,
-) -> Vec<Ipv4Addr> {
-    let IpAddr::V4(src_ip) = interface.ips.first().unwrap().ip() else {
-        panic!();
-    };
+fn build_probe(
+    link: &Link,
+    src_ip: IpAddr,
+    src_port: u16,
+    dest_ip: IpAddr,
+    probe: Probe,
+) -> Result<Vec<u8>> {
+    let src_socket = SocketAddr::new(src_ip, src_port);
 
-    let Ok(Channel::Ethernet(_, mut rx)) = datalink::channel(&interface, Default::default()) else {
-        panic!()
+    let frame = match (src_ip, dest_ip, probe) {
+        (IpAddr::V4(src_ip), IpAddr::V4(dest_ip), Probe::Arp) => {
+            arp::request(link.src_mac, src_ip, dest_ip)
+        }
+        (IpAddr::V4(src_ip), IpAddr::V4(dest_ip), Probe::Echo) => packet::build(
+            link.src_mac,
+            src_ip,
+            dest_ip,
+            link.next_hop(dest_ip.into())?,
+        )
+        .to_vec(),
+        (IpAddr::V6(src_ip), IpAddr::V6(dest_ip), Probe::Echo) => packet::build_v6(
+            link.src_mac,
+            src_ip,
+            dest_ip,
+            link.next_hop(dest_ip.into())?,
+        ),
+        (IpAddr::V6(src_ip), IpAddr::V6(dest_ip), Probe::NeighborSolicit) => {
+            packet::build_neighbor_solicit(link.src_mac, src_ip, dest_ip)
+        }
+        (_, _, Probe::Syn(port)) => scanner::packet::build(
+            link.src_mac,
+            src_socket,
+            SocketAddr::new(dest_ip, port),
+            link.next_hop(dest_ip)?,
+            TcpFlags::SYN,
+        ),
+        (_, _, Probe::Ack(port)) => scanner::packet::build(
+            link.src_mac,
+            src_socket,
+            SocketAddr::new(dest_ip, port),
+            link.next_hop(dest_ip)?,
+            TcpFlags::ACK,
+        ),
+        (_, _, Probe::Udp(port)) => scanner::packet::build_udp(
+            link.src_mac,
+            src_socket,
+            SocketAddr::new(dest_ip, port),
+            link.next_hop(dest_ip)?,
+            &[],
+        ),
+        _ => unreachable!("{:?} can not be sent to {}", probe, dest_ip),
     };
 
-    let mut reachable_ips = Vec::with_capacity(target_dests.len());
+    Ok(frame)
+}
 
-    loop {
-        let raw_packet = rx.next().unwrap();
-        let eth_packet = EthernetPacket::new(raw_packet).unwrap();
-        let ipv4_packet = Ipv4Packet::new(eth_packet.payload()).unwrap();
+fn receive_and_filter(
+    mut rx: Box<dyn Channel>,
+    link: Link,
+    src_port: u16,
+    state: Shared,
+    done: Arc<AtomicBool>,
+    pb: WeakProgressBar,
+) -> Result<Vec<Host>> {
+    let mut hosts = Vec::new();
+
+    while !done.load(Ordering::SeqCst) {
+        let Some(frame) = rx.recv(RECV_TIMEOUT)? else {
+            continue;
+        };
+        let Some(host) = answered_host(frame, &link, src_port) else {
+            continue;
+        };
 
-        if ipv4_packet.get_next_level_protocol() == IpNextHeaderProtocols::Icmp
-            && ipv4_packet.get_destination() == src_ip
-            && target_dests.contains(&ipv4_packet.get_source())
         {
-            let icmp_packet = IcmpPacket::new(ipv4_packet.payload()).unwrap();
+            let mut state = state.lock().unwrap();
+            let Some(sent_at) = state.sent.remove(&host.ip) else {
+                continue;
+            };
+            state.rtt.sample(host.ip, sent_at.elapsed());
+        }
+
+        pb.upgrade().unwrap().println(format!(
+            "  {} {} ({})",
+            "REACHABLE".green().bold(),
+            host.ip,
+            host.reason
+        ));
+
+        hosts.push(host);
+    }
 
-            if icmp_packet.get_icmp_type() == IcmpTypes::EchoReply {
-                let from = ipv4_packet.get_source();
+    Ok(hosts)
+}
+
+/// The host an answer addressed to us speaks for, and its MAC when it is
+/// a neighbor.
+fn answered_host(frame: &[u8], link: &Link, src_port: u16) -> Option<Host> {
+    if let Some(arp) = arp::parse(frame) {
+        return (arp.operation == ArpOperations::Reply && link.is_local(IpAddr::V4(arp.target_ip)))
+            .then_some(Host {
+                ip: IpAddr::V4(arp.sender_ip),
+                reason: Reason::ArpResponse,
+                mac: Some(arp.sender_mac),
+            });
+    }
+
+    let datagram = scanner::packet::parse(frame)?;
+    if !link.is_local(datagram.destination) {
+        return None;
+    }
 
-                pb.upgrade()
-                    .unwrap()
-                    .println(format!("  {} {}", "REACHABLE".green().bold(), from));
+    let reason = match datagram.protocol {
+        IpNextHeaderProtocols::Icmp => {
+            let icmp_packet = IcmpPacket::new(datagram.payload)?;
 
-                reachable_ips.push(from);
+            match icmp_packet.get_icmp_type() {
+                IcmpTypes::EchoReply => Reason::EchoReply,
+                IcmpTypes::DestinationUnreachable => unreachable_reason(&datagram, link, src_port)?,
+                _ => return None,
             }
         }
 
-        if DONE.load(Ordering::SeqCst) {
-            break;
+        IpNextHeaderProtocols::Icmpv6 => {
+            let icmp_packet = Icmpv6Packet::new(datagram.payload)?;
+
+            match icmp_packet.get_icmpv6_type() {
+                Icmpv6Types::EchoReply => Reason::EchoReply,
+                Icmpv6Types::DestinationUnreachable => {
+                    unreachable_reason(&datagram, link, src_port)?
+                }
+                Icmpv6Types::NeighborAdvert => {
+                    let (target, mac) = neighbor::advertisement(frame)?;
+                    return Some(Host {
+                        ip: IpAddr::V6(target),
+                        reason: Reason::NeighborAdvert,
+                        mac: Some(mac),
+                    });
+                }
+                _ => return None,
+            }
         }
-    }
 
-    reachable_ips
+        IpNextHeaderProtocols::Tcp => {
+            let tcp_packet = TcpPacket::new(datagram.payload)?;
+            let flags = tcp_packet.get_flags();
+
+            if tcp_packet.get_destination() != src_port {
+                return None;
+            } else if flags & TcpFlags::RST != 0 {
+                Reason::Reset
+            } else if flags & (TcpFlags::SYN | TcpFlags::ACK) == TcpFlags::SYN | TcpFlags::ACK {
+                Reason::SynAck
+            } else {
+                return None;
+            }
+        }
+
+        IpNextHeaderProtocols::Udp => UdpPacket::new(datagram.payload)
+            .filter(|x| x.get_destination() == src_port)
+            .map(|_| Reason::UdpResponse)?,
+
+        _ => return None,
+    };
+
+    Some(Host {
+        ip: datagram.source,
+        reason,
+        mac: None,
+    })
+}
+
+/// Destination unreachable counts when the host itself sends it about one
+/// of our probes. A router reporting the host unreachable says the
+/// opposite.
+fn unreachable_reason(datagram: &Datagram, link: &Link, src_port: u16) -> Option<Reason> {
+    let inner = scanner::packet::parse_ip(datagram.payload.get(ICMP_HEADER_LEN..)?)?;
+    let inner_port = u16::from_be_bytes([*inner.payload.first()?, *inner.payload.get(1)?]);
+
+    (link.is_local(inner.source) && inner.destination == datagram.source && inner_port == src_port)
+        .then_some(Reason::Unreachable)
 }
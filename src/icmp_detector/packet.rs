@@ -1,4 +1,4 @@
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 use pnet::{
     packet::{
@@ -7,6 +7,10 @@ use pnet::{
             echo_request::{IcmpCodes, MutableEchoRequestPacket},
             IcmpTypes,
         },
+        icmpv6::{
+            self, echo_request::MutableEchoRequestPacket as MutableEchoRequestV6Packet,
+            ndp::MutableNeighborSolicitPacket, Icmpv6Packet, Icmpv6Types,
+        },
         ip::IpNextHeaderProtocols,
         ipv4::{self, Ipv4Flags, MutableIpv4Packet},
         util, Packet,
@@ -15,7 +19,13 @@ use pnet::{
 };
 use rand::random;
 
-use crate::config::{ETHERNET_HEADER_LEN, ICMP_ECHO_REQUEST_LEN, IPV4_HEADER_LEN};
+use crate::{
+    config::{ETHERNET_HEADER_LEN, ICMP_ECHO_REQUEST_LEN, IPV4_HEADER_LEN},
+    scanner::packet::ip_frame,
+};
+
+/// Neighbor solicitation with a source link-layer address option.
+const NEIGHBOR_SOLICIT_LEN: usize = 32;
 
 pub fn build(
     src_mac: MacAddr,
@@ -61,3 +71,75 @@ pub fn build(
 
     packet_buf
 }
+
+pub fn build_v6(
+    src_mac: MacAddr,
+    src_ip: Ipv6Addr,
+    dest_ip: Ipv6Addr,
+    gateway_mac: MacAddr,
+) -> Vec<u8> {
+    let mut icmp_buf = [0_u8; ICMP_ECHO_REQUEST_LEN];
+
+    let mut icmp_packet = MutableEchoRequestV6Packet::new(&mut icmp_buf).unwrap();
+    icmp_packet.set_icmpv6_type(Icmpv6Types::EchoRequest);
+    icmp_packet.set_identifier(random::<u16>());
+    icmp_packet.set_sequence_number(1);
+    set_checksum(&mut icmp_buf, src_ip, dest_ip);
+
+    ip_frame(
+        src_mac,
+        gateway_mac,
+        IpAddr::V6(src_ip),
+        IpAddr::V6(dest_ip),
+        IpNextHeaderProtocols::Icmpv6,
+        &icmp_buf,
+    )
+}
+
+/// Asks the solicited-node multicast group of `target` for its link-layer
+/// address, the IPv6 counterpart of an ARP request.
+pub fn build_neighbor_solicit(src_mac: MacAddr, src_ip: Ipv6Addr, target: Ipv6Addr) -> Vec<u8> {
+    let mut icmp_buf = [0_u8; NEIGHBOR_SOLICIT_LEN];
+
+    let mut icmp_packet = MutableNeighborSolicitPacket::new(&mut icmp_buf).unwrap();
+    icmp_packet.set_icmpv6_type(Icmpv6Types::NeighborSolicit);
+    icmp_packet.set_target_addr(target);
+
+    // source link-layer address option, 1 unit of 8 bytes
+    icmp_buf[24] = 1;
+    icmp_buf[25] = 1;
+    icmp_buf[26..].copy_from_slice(&src_mac.octets());
+
+    let group = solicited_node(target);
+    set_checksum(&mut icmp_buf, src_ip, group);
+
+    let [.., a, b, c, d] = group.octets();
+    ip_frame(
+        src_mac,
+        MacAddr(0x33, 0x33, a, b, c, d),
+        IpAddr::V6(src_ip),
+        IpAddr::V6(group),
+        IpNextHeaderProtocols::Icmpv6,
+        &icmp_buf,
+    )
+}
+
+/// ff02::1:ffXX:XXXX, where XX:XXXX are the low 24 bits of `target`.
+fn solicited_node(target: Ipv6Addr) -> Ipv6Addr {
+    let [.., a, b, c] = target.octets();
+    Ipv6Addr::new(
+        0xff02,
+        0,
+        0,
+        0,
+        0,
+        1,
+        0xff00 | a as u16,
+        u16::from_be_bytes([b, c]),
+    )
+}
+
+fn set_checksum(icmp_buf: &mut [u8], src_ip: Ipv6Addr, dest_ip: Ipv6Addr) {
+    let checksum = icmpv6::checksum(&Icmpv6Packet::new(icmp_buf).unwrap(), &src_ip, &dest_ip);
+    icmp_buf[2..4].copy_from_slice(&checksum.to_be_bytes());
+}
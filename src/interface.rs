@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use pnet::{
+    datalink::{self, NetworkInterface},
+    util::MacAddr,
+};
+
+use crate::{
+    error::{Result, ScanError},
+    route,
+};
+
+/// Addresses every outgoing frame is built from.
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub src_mac: MacAddr,
+    pub src_ipv4: Option<Ipv4Addr>,
+    pub src_ipv6: Option<Ipv6Addr>,
+    /// Only looked up when some host is off our subnets.
+    pub gateway_mac: Option<MacAddr>,
+    /// Hosts on our own subnet and the MACs they answered discovery from.
+    pub neighbors: HashMap<IpAddr, MacAddr>,
+}
+
+impl Link {
+    pub fn new(interface: &NetworkInterface, gateway_mac: Option<MacAddr>) -> Result<Self> {
+        let src_ipv4 = ipv4(interface).ok();
+        let src_ipv6 = ipv6(interface);
+
+        if src_ipv4.is_none() && src_ipv6.is_none() {
+            return Err(ScanError::Interface(format!(
+                "{} has no IP address",
+                interface.name
+            )));
+        }
+
+        Ok(Link {
+            src_mac: mac(interface)?,
+            src_ipv4,
+            src_ipv6,
+            gateway_mac,
+            neighbors: HashMap::new(),
+        })
+    }
+
+    pub fn with_neighbors(mut self, neighbors: HashMap<IpAddr, MacAddr>) -> Self {
+        self.neighbors = neighbors;
+        self
+    }
+
+    /// The MAC a frame to `dest` is sent to: the host itself when it is
+    /// a known neighbor, the gateway otherwise.
+    pub fn next_hop(&self, dest: IpAddr) -> Result<MacAddr> {
+        self.neighbors
+            .get(&dest)
+            .copied()
+            .or(self.gateway_mac)
+            .ok_or_else(|| ScanError::Interface(format!("no gateway MAC to reach {}", dest)))
+    }
+
+    /// Our address in the family of `dest`.
+    pub fn source(&self, dest: IpAddr) -> Result<IpAddr> {
+        match dest {
+            IpAddr::V4(_) => self.src_ipv4.map(IpAddr::V4),
+            IpAddr::V6(_) => self.src_ipv6.map(IpAddr::V6),
+        }
+        .ok_or_else(|| ScanError::Interface(format!("no source address to reach {}", dest)))
+    }
+
+    /// Whether `ip` is one of the addresses frames are sent from.
+    pub fn is_local(&self, ip: IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(ip) => self.src_ipv4 == Some(ip),
+            IpAddr::V6(ip) => self.src_ipv6 == Some(ip),
+        }
+    }
+}
+
+/// Which interface to scan from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Selector {
+    /// The interface the preferred default route leaves through.
+    #[default]
+    Auto,
+    Name(String),
+    /// The interface holding this address, primary or not.
+    Address(IpAddr),
+}
+
+impl From<Ipv4Addr> for Selector {
+    fn from(ip: Ipv4Addr) -> Self {
+        Selector::Address(IpAddr::V4(ip))
+    }
+}
+
+impl From<IpAddr> for Selector {
+    fn from(ip: IpAddr) -> Self {
+        Selector::Address(ip)
+    }
+}
+
+/// An address selects by address, `auto` by route, anything else by name.
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "" => Err("empty interface".into()),
+            "auto" => Ok(Selector::Auto),
+            _ => Ok(s
+                .parse()
+                .map(Selector::Address)
+                .unwrap_or_else(|_| Selector::Name(s.into()))),
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Selector::Auto => write!(f, "auto"),
+            Selector::Name(name) => write!(f, "{}", name),
+            Selector::Address(ip) => write!(f, "{}", ip),
+        }
+    }
+}
+
+pub fn find(selector: &Selector) -> Result<NetworkInterface> {
+    select(&datalink::interfaces(), selector)
+}
+
+/// The interface among `interfaces` that `selector` picks. One picked by
+/// address has that address moved to the front, so frames of its family
+/// are sent from it rather than the interface's primary address.
+pub fn select(interfaces: &[NetworkInterface], selector: &Selector) -> Result<NetworkInterface> {
+    let found = match selector {
+        Selector::Auto => route::default_routes()
+            .iter()
+            .find_map(|route| interfaces.iter().find(|x| x.name == route.interface)),
+        Selector::Name(name) => interfaces.iter().find(|x| &x.name == name),
+        Selector::Address(ip) => interfaces
+            .iter()
+            .find(|x| x.ips.iter().any(|n| n.ip() == *ip)),
+    };
+
+    let mut found = found.cloned().ok_or_else(|| {
+        let reason = match selector {
+            Selector::Auto => "no default route leaves through any interface".into(),
+            Selector::Name(name) => format!("no interface is named {}", name),
+            Selector::Address(ip) => format!("no interface has the address {}", ip),
+        };
+        ScanError::Interface(format!("{}, available: {}", reason, describe(interfaces)))
+    })?;
+
+    if let Selector::Address(ip) = selector {
+        found.ips.sort_by_key(|n| n.ip() != *ip);
+    }
+
+    Ok(found)
+}
+
+/// `eth0 (10.0.0.2, fe80::1), lo (127.0.0.1)`
+fn describe(interfaces: &[NetworkInterface]) -> String {
+    if interfaces.is_empty() {
+        return "none".into();
+    }
+
+    interfaces
+        .iter()
+        .map(|x| {
+            let ips: Vec<String> = x.ips.iter().map(|n| n.ip().to_string()).collect();
+            format!("{} ({})", x.name, ips.join(", "))
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+pub fn ipv4(interface: &NetworkInterface) -> Result<Ipv4Addr> {
+    interface
+        .ips
+        .iter()
+        .find_map(|n| match n.ip() {
+            IpAddr::V4(ip) => Some(ip),
+            IpAddr::V6(_) => None,
+        })
+        .ok_or_else(|| ScanError::Interface(format!("{} has no IPv4 address", interface.name)))
+}
+
+/// The first global IPv6 address, or the link-local one when there is
+/// nothing else. A link-local source could not reach anything beyond the
+/// link, so it is not preferred even when the interface was selected by it.
+pub fn ipv6(interface: &NetworkInterface) -> Option<Ipv6Addr> {
+    let ips: Vec<Ipv6Addr> = interface
+        .ips
+        .iter()
+        .filter_map(|n| match n.ip() {
+            IpAddr::V4(_) => None,
+            IpAddr::V6(ip) => Some(ip),
+        })
+        .collect();
+
+    ips.iter()
+        .find(|ip| !is_link_local(ip))
+        .or_else(|| ips.first())
+        .copied()
+}
+
+fn is_link_local(ip: &Ipv6Addr) -> bool {
+    ip.segments()[0] & 0xffc0 == 0xfe80
+}
+
+/// Whether `ip` lies in one of the subnets `interface` is attached to.
+pub fn is_on_link(interface: &NetworkInterface, ip: IpAddr) -> bool {
+    interface.ips.iter().any(|n| n.contains(ip))
+}
+
+pub fn mac(interface: &NetworkInterface) -> Result<MacAddr> {
+    interface
+        .mac
+        .ok_or_else(|| ScanError::Interface(format!("{} has no MAC address", interface.name)))
+}
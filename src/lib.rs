@@ -0,0 +1,385 @@
+pub mod arp;
+pub mod config;
+pub mod display;
+pub mod error;
+pub mod icmp_detector;
+pub mod interface;
+pub mod neighbor;
+pub mod port_spec;
+pub mod rate_limiter;
+pub mod route;
+pub mod scanner;
+#[cfg(feature = "simnet")]
+pub mod simnet;
+pub mod timing;
+pub mod toml_parser;
+pub mod transport;
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use colored::Colorize;
+use pnet::util::MacAddr;
+
+pub use error::{Result, ScanError};
+pub use icmp_detector::{Discovery, Reason};
+pub use interface::Selector;
+pub use port_spec::PortSpec;
+pub use scanner::{PortResult, PortState, Protocol, ScanType};
+pub use timing::{Timing, TimingTemplate};
+
+use icmp_detector::Host;
+use rate_limiter::RateLimiter;
+use scanner::Target;
+use timing::RttTable;
+use transport::{DatalinkTransport, Transport};
+
+/// Hosts and the ports to probe on every one of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetSpec {
+    pub ips: Vec<IpAddr>,
+    pub ports: Vec<u16>,
+    pub udp_ports: Vec<u16>,
+}
+
+/// Everything a scan needs to know: where to send from and what to probe.
+#[derive(Debug, Clone)]
+pub struct ScanConfig {
+    pub interface: Selector,
+    /// Looked up from the default route when not given.
+    pub gateway_mac: Option<MacAddr>,
+    /// Each host is only probed on the ports of the targets it is in.
+    pub targets: Vec<TargetSpec>,
+    pub scan_type: ScanType,
+    pub discovery: Discovery,
+    /// Treat every host as up and port scan it without discovery.
+    pub skip_discovery: bool,
+    /// Stop after discovery, the result only lists the hosts that are up.
+    pub discovery_only: bool,
+    pub timing: Timing,
+}
+
+impl ScanConfig {
+    /// `gateway_mac` may be `None` to look it up when an off-link host
+    /// needs it.
+    pub fn new(
+        interface: impl Into<Selector>,
+        gateway_mac: impl Into<Option<MacAddr>>,
+        ips: Vec<IpAddr>,
+        ports: Vec<u16>,
+    ) -> Self {
+        ScanConfig {
+            interface: interface.into(),
+            gateway_mac: gateway_mac.into(),
+            targets: vec![TargetSpec {
+                ips,
+                ports,
+                udp_ports: Vec::new(),
+            }],
+            scan_type: ScanType::default(),
+            discovery: Discovery::default(),
+            skip_discovery: false,
+            discovery_only: false,
+            timing: Timing::default(),
+        }
+    }
+
+    /// Every host of every target, once.
+    pub fn hosts(&self) -> Vec<IpAddr> {
+        self.targets
+            .iter()
+            .flat_map(|x| x.ips.iter().copied())
+            .collect::<BTreeSet<IpAddr>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Typed outcome of a scan.
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    pub reachable: Vec<IpAddr>,
+    /// Why each reachable host was deemed up.
+    pub reasons: BTreeMap<IpAddr, Reason>,
+    /// MACs of the reachable hosts found on our own subnet.
+    pub macs: BTreeMap<IpAddr, MacAddr>,
+    pub open: Vec<PortResult>,
+    pub closed: Vec<PortResult>,
+    pub filtered: Vec<PortResult>,
+    pub unfiltered: Vec<PortResult>,
+    pub open_filtered: Vec<PortResult>,
+}
+
+impl ScanResult {
+    pub(crate) fn push(&mut self, state: PortState, result: PortResult) {
+        match state {
+            PortState::Open => self.open.push(result),
+            PortState::Closed => self.closed.push(result),
+            PortState::Filtered => self.filtered.push(result),
+            PortState::Unfiltered => self.unfiltered.push(result),
+            PortState::OpenFiltered => self.open_filtered.push(result),
+        }
+    }
+}
+
+pub struct Scanner {
+    config: ScanConfig,
+    transport: Option<Arc<dyn Transport>>,
+}
+
+impl Scanner {
+    pub fn new(config: ScanConfig) -> Self {
+        Scanner {
+            config,
+            transport: None,
+        }
+    }
+
+    /// Sends and receives through `transport` instead of the interface
+    /// `config.interface` selects.
+    pub fn with_transport(config: ScanConfig, transport: Arc<dyn Transport>) -> Self {
+        Scanner {
+            config,
+            transport: Some(transport),
+        }
+    }
+
+    pub fn config(&self) -> &ScanConfig {
+        &self.config
+    }
+
+    /// Runs host discovery over the configured hosts, then probes every TCP
+    /// port with the configured scan type and every UDP port of the hosts
+    /// that answered. Round trips measured along the way decide how long
+    /// each host gets before a probe is retried.
+    ///
+    /// A SYN scan without raw socket access falls back to a connect scan;
+    /// the other raw scan types and discovery on its own have no
+    /// unprivileged equivalent and fail.
+    pub fn run(&self) -> Result<ScanResult> {
+        if self.config.skip_discovery && self.config.discovery_only {
+            return Err(ScanError::Config(
+                "skip_discovery and discovery_only can not be combined".into(),
+            ));
+        }
+
+        // off-link hosts would get no probe at all and count as down
+        if !self.config.skip_discovery && self.config.discovery.is_empty() {
+            return Err(ScanError::Config(
+                "discovery sends no probe, enable echo or give it ports".into(),
+            ));
+        }
+
+        if self.config.scan_type == ScanType::Connect {
+            if self.config.discovery_only {
+                return Err(ScanError::Config(
+                    "discovery_only needs raw socket access, not a connect scan".into(),
+                ));
+            }
+            return self.run_connect();
+        }
+
+        match self.run_raw() {
+            Err(ScanError::Permission(msg))
+                if self.config.scan_type == ScanType::Syn && !self.config.discovery_only =>
+            {
+                println!(
+                    "{} {}",
+                    "NO RAW SOCKET ACCESS, FALLING BACK TO CONNECT SCAN:"
+                        .yellow()
+                        .bold(),
+                    msg
+                );
+                self.run_connect()
+            }
+            result => result,
+        }
+    }
+
+    fn run_raw(&self) -> Result<ScanResult> {
+        let transport = match &self.transport {
+            Some(transport) => transport.clone(),
+            None => Arc::new(DatalinkTransport::new(interface::find(
+                &self.config.interface,
+            )?)),
+        };
+
+        // looked up only once some host turns out to need it
+        let mut gateway_mac = self.config.gateway_mac;
+
+        let limiter = Arc::new(RateLimiter::new(self.config.timing.rate));
+
+        let (hosts, rtt) = if self.config.skip_discovery {
+            let hosts = self
+                .config
+                .hosts()
+                .into_iter()
+                .map(|ip| Host {
+                    ip,
+                    reason: Reason::UserSet,
+                    mac: None,
+                })
+                .collect();
+            (hosts, RttTable::new(self.config.timing))
+        } else {
+            let hosts = self.config.hosts();
+            if let Some(dest) = hosts
+                .iter()
+                .find(|x| !interface::is_on_link(transport.interface(), **x))
+            {
+                resolve_gateway(transport.as_ref(), &limiter, *dest, &mut gateway_mac)?;
+            }
+
+            icmp_detector::detect(
+                transport.clone(),
+                gateway_mac,
+                &self.config.discovery,
+                hosts,
+                RttTable::new(self.config.timing),
+                limiter.clone(),
+            )?
+        };
+
+        let reachable: Vec<IpAddr> = hosts.iter().map(|x| x.ip).collect();
+        let mut neighbors: HashMap<IpAddr, MacAddr> =
+            hosts.iter().filter_map(|x| Some((x.ip, x.mac?))).collect();
+
+        // hosts on our subnet discovery did not resolve, skipped discovery
+        // leaves all of them unresolved
+        let unresolved: Vec<IpAddr> = reachable
+            .iter()
+            .filter(|ip| {
+                interface::is_on_link(transport.interface(), **ip) && !neighbors.contains_key(ip)
+            })
+            .copied()
+            .collect();
+        if !unresolved.is_empty() && !self.config.discovery_only {
+            neighbors.extend(neighbor::resolve(
+                transport.as_ref(),
+                &unresolved,
+                &limiter,
+            )?);
+        }
+
+        if self.config.discovery_only {
+            return Ok(ScanResult {
+                reachable,
+                reasons: hosts.iter().map(|x| (x.ip, x.reason)).collect(),
+                macs: neighbors.into_iter().collect(),
+                ..ScanResult::default()
+            });
+        }
+
+        // off-link hosts and neighbors that never answered go through the
+        // gateway
+        if let Some(dest) = reachable.iter().find(|x| !neighbors.contains_key(x)) {
+            resolve_gateway(transport.as_ref(), &limiter, *dest, &mut gateway_mac)?;
+        }
+
+        let mut result = scanner::scan(
+            transport,
+            gateway_mac,
+            neighbors.clone(),
+            self.config.scan_type,
+            self.targets(&reachable),
+            rtt,
+            limiter,
+        )?;
+        result.reachable = reachable;
+        result.reasons = hosts.iter().map(|x| (x.ip, x.reason)).collect();
+        result.macs = neighbors.into_iter().collect();
+
+        Ok(result)
+    }
+
+    /// Pinging needs raw access too, so every host is probed and the ones
+    /// that answered on any port are reported reachable, with the answer
+    /// that connect saw as the reason.
+    fn run_connect(&self) -> Result<ScanResult> {
+        let limiter = Arc::new(RateLimiter::new(self.config.timing.rate));
+
+        let mut result = scanner::connect::scan(
+            self.targets(&self.config.hosts()),
+            RttTable::new(self.config.timing),
+            limiter,
+        )?;
+
+        let answers = result
+            .open
+            .iter()
+            .map(|x| (x, PortState::Open))
+            .chain(result.closed.iter().map(|x| (x, PortState::Closed)));
+
+        for (answer, state) in answers {
+            let reason = match (answer.protocol, state) {
+                (Protocol::Tcp, PortState::Open) => Reason::SynAck,
+                (Protocol::Tcp, _) => Reason::Reset,
+                (Protocol::Udp, PortState::Open) => Reason::UdpResponse,
+                (Protocol::Udp, _) => Reason::Unreachable,
+            };
+            result.reasons.entry(answer.socket.ip()).or_insert(reason);
+        }
+
+        result.reachable = result.reasons.keys().copied().collect();
+
+        Ok(result)
+    }
+
+    /// The sockets the targets pair `ips` with, each once however many
+    /// targets name it.
+    fn targets(&self, ips: &[IpAddr]) -> Vec<Target> {
+        let ips: HashSet<IpAddr> = ips.iter().copied().collect();
+        let mut targets = BTreeSet::new();
+
+        for spec in &self.config.targets {
+            let spec_ips: Vec<IpAddr> = spec
+                .ips
+                .iter()
+                .filter(|x| ips.contains(x))
+                .copied()
+                .collect();
+
+            let tcp = get_socket_addr(&spec_ips, &spec.ports)
+                .into_iter()
+                .map(|socket| (Protocol::Tcp, socket));
+            let udp = get_socket_addr(&spec_ips, &spec.udp_ports)
+                .into_iter()
+                .map(|socket| (Protocol::Udp, socket));
+
+            targets.extend(tcp.chain(udp));
+        }
+
+        targets.into_iter().collect()
+    }
+}
+
+/// Fills in the gateway MAC from the default route `dest` takes unless it
+/// is known.
+fn resolve_gateway(
+    transport: &dyn Transport,
+    limiter: &RateLimiter,
+    dest: IpAddr,
+    gateway_mac: &mut Option<MacAddr>,
+) -> Result<()> {
+    if gateway_mac.is_none() {
+        let mac = route::gateway_mac(transport, limiter, dest)?;
+        println!("{}: {}", "GATEWAY MAC".green().bold(), mac);
+        *gateway_mac = Some(mac);
+    }
+    Ok(())
+}
+
+pub fn get_socket_addr(dest_ips: &[IpAddr], dest_ports: &[u16]) -> Vec<SocketAddr> {
+    let mut pairs = Vec::with_capacity(dest_ips.len() * dest_ports.len());
+
+    for ip in dest_ips {
+        for port in dest_ports {
+            pairs.push(SocketAddr::new(*ip, *port));
+        }
+    }
+
+    pairs
+}
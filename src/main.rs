@@ -1,48 +1,27 @@
 mod cli;
-mod config;
-mod display;
-mod icmp_detector;
-mod scanner;
-mod toml_parser;
 
-use std::net::{Ipv4Addr, SocketAddrV4};
+use std::process;
 
-fn main() {
-    let (profile_path, output_path) = cli::get_args();
-
-    // let profile = toml_parser::parse("example/test.toml");
-    let profile = toml_parser::parse(profile_path);
-
-    let reachable_ips = icmp_detector::detect(
-        profile.interface_ip,
-        profile.gateway_mac,
-        profile.ip_vec.clone(),
-    );
-
-    let socket_addr = get_socket_addr(&reachable_ips, &profile.ports_vec);
-
-    let (open_ports, closed_ports, filtered_ports) =
-        scanner::scan(profile.interface_ip, profile.gateway_mac, socket_addr);
+use colored::Colorize;
+use syn_port_scanner::{display, toml_parser, ScanError, Scanner};
 
-    display::display(
-        open_ports,
-        closed_ports,
-        filtered_ports,
-        profile.show_open,
-        profile.show_closed,
-        profile.show_filtered,
-        output_path,
-    );
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{} {}", "ERROR:".red().bold(), e);
+        process::exit(e.exit_code());
+    }
 }
 
-fn get_socket_addr(dest_ips: &[Ipv4Addr], dest_ports: &[u16]) -> Vec<SocketAddrV4> {
-    let mut pairs = Vec::with_capacity(dest_ips.len() * dest_ports.len());
+fn run() -> Result<(), ScanError> {
+    let args = cli::get_args();
 
-    for ip in dest_ips {
-        for port in dest_ports {
-            pairs.push(SocketAddrV4::new(*ip, *port));
-        }
-    }
+    let mut profile = toml_parser::parse(&args.profile_path)?;
+    args.apply_timing(&mut profile.scan.timing);
+    args.apply_interface(&mut profile.scan);
+    args.apply_discovery(&mut profile.scan);
+    args.apply_ports(&mut profile);
+
+    let result = Scanner::new(profile.scan).run()?;
 
-    pairs
+    display::display(&result, &profile.show, &profile.services, args.output_path)
 }
@@ -0,0 +1,135 @@
+//! Resolves the MACs of hosts on our own subnets, so frames to them go
+//! straight to the host instead of through the gateway. The transport's
+//! neighbour cache is asked first, ARP or neighbor solicitation after.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+    time::{Duration, Instant},
+};
+
+use pnet::{
+    packet::{
+        arp::ArpOperations,
+        ethernet::EthernetPacket,
+        icmpv6::{ndp::NeighborAdvertPacket, Icmpv6Types},
+        ip::IpNextHeaderProtocols,
+    },
+    util::MacAddr,
+};
+
+use crate::{
+    arp,
+    error::Result,
+    icmp_detector, interface,
+    rate_limiter::RateLimiter,
+    scanner,
+    transport::{Channel, Transport},
+};
+
+const RESOLVE_ATTEMPTS: usize = 3;
+
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The MACs of every host in `ips` that answered. Each request round goes
+/// out in one sweep paced by `limiter`, only the hosts still missing are
+/// asked again.
+pub fn resolve(
+    transport: &dyn Transport,
+    ips: &[IpAddr],
+    limiter: &RateLimiter,
+) -> Result<HashMap<IpAddr, MacAddr>> {
+    let mut neighbors: HashMap<IpAddr, MacAddr> = ips
+        .iter()
+        .filter_map(|ip| match ip {
+            IpAddr::V4(ipv4) => Some((*ip, transport.neighbor(*ipv4)?)),
+            IpAddr::V6(_) => None,
+        })
+        .collect();
+
+    let interface = transport.interface();
+    let src_mac = interface::mac(interface)?;
+    let src_ipv4 = interface::ipv4(interface).ok();
+    let src_ipv6 = interface::ipv6(interface);
+    let mut channel = transport.open()?;
+
+    for _ in 0..RESOLVE_ATTEMPTS {
+        let missing: Vec<IpAddr> = ips
+            .iter()
+            .filter(|ip| !neighbors.contains_key(ip))
+            .copied()
+            .collect();
+        if missing.is_empty() {
+            break;
+        }
+
+        for ip in &missing {
+            let request = match (ip, src_ipv4, src_ipv6) {
+                (IpAddr::V4(ip), Some(src_ip), _) => arp::request(src_mac, src_ip, *ip),
+                (IpAddr::V6(ip), _, Some(src_ip)) => {
+                    icmp_detector::packet::build_neighbor_solicit(src_mac, src_ip, *ip)
+                }
+                _ => continue,
+            };
+            limiter.acquire();
+            channel.send(&request)?;
+        }
+
+        receive(channel.as_mut(), &missing, &mut neighbors)?;
+    }
+
+    Ok(neighbors)
+}
+
+/// Collects answers about `missing` hosts until all of them answered or
+/// `RESOLVE_TIMEOUT` passed.
+fn receive(
+    channel: &mut dyn Channel,
+    missing: &[IpAddr],
+    neighbors: &mut HashMap<IpAddr, MacAddr>,
+) -> Result<()> {
+    let deadline = Instant::now() + RESOLVE_TIMEOUT;
+
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        if missing.iter().all(|ip| neighbors.contains_key(ip)) {
+            break;
+        }
+
+        let Some(frame) = channel.recv(left)? else {
+            continue;
+        };
+
+        let answer = match arp::parse(frame) {
+            Some(arp) if arp.operation == ArpOperations::Reply => {
+                Some((IpAddr::V4(arp.sender_ip), arp.sender_mac))
+            }
+            Some(_) => None,
+            None => advertisement(frame).map(|(ip, mac)| (IpAddr::V6(ip), mac)),
+        };
+
+        if let Some((ip, mac)) = answer.filter(|(ip, _)| missing.contains(ip)) {
+            neighbors.insert(ip, mac);
+        }
+    }
+
+    Ok(())
+}
+
+/// The address a neighbor advertisement announces and the MAC it came
+/// from.
+pub(crate) fn advertisement(frame: &[u8]) -> Option<(Ipv6Addr, MacAddr)> {
+    let datagram = scanner::packet::parse(frame)?;
+    if datagram.protocol != IpNextHeaderProtocols::Icmpv6 {
+        return None;
+    }
+
+    let advert = NeighborAdvertPacket::new(datagram.payload)?;
+    if advert.get_icmpv6_type() != Icmpv6Types::NeighborAdvert {
+        return None;
+    }
+
+    Some((
+        advert.get_target_addr(),
+        EthernetPacket::new(frame)?.get_source(),
+    ))
+}
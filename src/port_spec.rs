@@ -0,0 +1,82 @@
+use std::str::FromStr;
+
+use crate::Protocol;
+
+/// Ports written the way nmap's `-p` takes them, e.g.
+/// `22,80,8000-8100,U:53,T:3389`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortSpec {
+    /// Ports before any `T:`/`U:` prefix, probed with the target's protocol.
+    pub ports: Vec<u16>,
+    pub tcp: Vec<u16>,
+    pub udp: Vec<u16>,
+}
+
+impl PortSpec {
+    /// TCP and UDP ports, the unprefixed ones going to `protocol`.
+    pub fn split(self, protocol: Protocol) -> (Vec<u16>, Vec<u16>) {
+        let (mut tcp, mut udp) = (self.tcp, self.udp);
+        match protocol {
+            Protocol::Tcp => tcp.extend(self.ports),
+            Protocol::Udp => udp.extend(self.ports),
+        }
+        (tcp, udp)
+    }
+}
+
+impl FromStr for PortSpec {
+    type Err = String;
+
+    /// A prefix holds until the next one. Ranges may leave out either end,
+    /// `-1024` starts at 1 and `60000-` runs up to 65535.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut spec = PortSpec::default();
+        let mut protocol = None;
+
+        for item in s.split(',').map(str::trim) {
+            let item = match item.split_once(':') {
+                Some((prefix, rest)) => {
+                    protocol = match prefix.to_ascii_uppercase().as_str() {
+                        "T" => Some(Protocol::Tcp),
+                        "U" => Some(Protocol::Udp),
+                        _ => return Err(format!("unsupported protocol prefix: {}", item)),
+                    };
+                    rest.trim()
+                }
+                None => item,
+            };
+
+            let ports = match protocol {
+                None => &mut spec.ports,
+                Some(Protocol::Tcp) => &mut spec.tcp,
+                Some(Protocol::Udp) => &mut spec.udp,
+            };
+
+            match item.split_once('-') {
+                Some((from, to)) => {
+                    let from = if from.is_empty() {
+                        1
+                    } else {
+                        parse_port(from)?
+                    };
+                    let to = if to.is_empty() {
+                        65535
+                    } else {
+                        parse_port(to)?
+                    };
+                    if from > to {
+                        return Err(format!("invalid port range: {}", item));
+                    }
+                    ports.extend(from..=to);
+                }
+                None => ports.push(parse_port(item)?),
+            }
+        }
+
+        Ok(spec)
+    }
+}
+
+fn parse_port(s: &str) -> Result<u16, String> {
+    s.trim().parse().map_err(|_| format!("invalid port: {}", s))
+}
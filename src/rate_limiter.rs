@@ -0,0 +1,66 @@
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A token bucket keeping every sender of a scan under one packets per
+/// second budget.
+pub struct RateLimiter {
+    rate: Option<f64>,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    capacity: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// `rate` in packets per second, `None` to send as fast as possible.
+    pub fn new(rate: Option<f64>) -> Self {
+        // allow bursts of 10 ms worth of packets, but never less than one
+        let capacity = rate.map_or(1.0, |x| (x / 100.0).max(1.0));
+
+        RateLimiter {
+            rate,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Blocks until one more packet fits into the budget.
+    pub fn acquire(&self) {
+        let Some(rate) = self.rate else {
+            return;
+        };
+
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+
+                bucket.tokens = (bucket.tokens + elapsed * rate).min(bucket.capacity);
+                bucket.refilled_at = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+
+                Duration::from_secs_f64((1.0 - bucket.tokens) / rate)
+            };
+
+            thread::sleep(wait);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! The kernel's routing and neighbour tables, read from procfs. Anywhere
+//! else they read as empty and the profile has to fill in the gaps.
+
+use std::{
+    fs,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use pnet::util::MacAddr;
+
+use crate::{
+    error::{Result, ScanError},
+    neighbor,
+    rate_limiter::RateLimiter,
+    transport::Transport,
+};
+
+const ROUTE_TABLE: &str = "/proc/net/route";
+
+const IPV6_ROUTE_TABLE: &str = "/proc/net/ipv6_route";
+
+const NEIGHBOR_TABLE: &str = "/proc/net/arp";
+
+const RTF_UP: u16 = 0x1;
+
+const RTF_GATEWAY: u16 = 0x2;
+
+/// ARP entry flag of a complete entry.
+const ATF_COM: u16 = 0x2;
+
+/// A default route: packets nothing more specific matches leave through
+/// `interface` to `gateway`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultRoute {
+    pub interface: String,
+    pub gateway: IpAddr,
+    pub metric: u32,
+}
+
+/// The default routes of `/proc/net/route` and then `/proc/net/ipv6_route`,
+/// each family's preferred one first.
+pub fn default_routes() -> Vec<DefaultRoute> {
+    let read = |path, parse: fn(&str) -> Vec<DefaultRoute>| {
+        fs::read_to_string(path)
+            .map(|table| parse(&table))
+            .unwrap_or_default()
+    };
+
+    let mut routes = read(ROUTE_TABLE, parse_default_routes);
+    routes.extend(read(IPV6_ROUTE_TABLE, parse_default_ipv6_routes));
+    routes
+}
+
+/// Parses the format of `/proc/net/route`, where addresses are hex in
+/// host byte order.
+pub fn parse_default_routes(table: &str) -> Vec<DefaultRoute> {
+    let mut routes: Vec<DefaultRoute> = table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let destination = u32::from_str_radix(fields.get(1)?, 16).ok()?;
+            let gateway = u32::from_str_radix(fields.get(2)?, 16).ok()?;
+            let flags = u16::from_str_radix(fields.get(3)?, 16).ok()?;
+            let metric = fields.get(6)?.parse().ok()?;
+            let mask = u32::from_str_radix(fields.get(7)?, 16).ok()?;
+
+            let is_default = destination == 0 && mask == 0;
+            let is_up = flags & (RTF_UP | RTF_GATEWAY) == RTF_UP | RTF_GATEWAY;
+
+            (is_default && is_up).then(|| DefaultRoute {
+                interface: fields[0].to_string(),
+                gateway: Ipv4Addr::from(gateway.to_le_bytes()).into(),
+                metric,
+            })
+        })
+        .collect();
+
+    routes.sort_by_key(|x| x.metric);
+    routes
+}
+
+/// Parses the format of `/proc/net/ipv6_route`, where addresses are hex
+/// in network byte order and numbers are hex too.
+pub fn parse_default_ipv6_routes(table: &str) -> Vec<DefaultRoute> {
+    let mut routes: Vec<DefaultRoute> = table
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let destination = u128::from_str_radix(fields.first()?, 16).ok()?;
+            let prefix_len = u8::from_str_radix(fields.get(1)?, 16).ok()?;
+            let gateway = u128::from_str_radix(fields.get(4)?, 16).ok()?;
+            let metric = u32::from_str_radix(fields.get(5)?, 16).ok()?;
+            let flags = u32::from_str_radix(fields.get(8)?, 16).ok()?;
+            let interface = fields.get(9)?;
+
+            let is_default = destination == 0 && prefix_len == 0;
+            let is_up = flags & u32::from(RTF_UP | RTF_GATEWAY) == u32::from(RTF_UP | RTF_GATEWAY);
+
+            (is_default && is_up).then(|| DefaultRoute {
+                interface: interface.to_string(),
+                gateway: Ipv6Addr::from(gateway).into(),
+                metric,
+            })
+        })
+        .collect();
+
+    routes.sort_by_key(|x| x.metric);
+    routes
+}
+
+/// The MAC the kernel has cached for `ip` in `/proc/net/arp`.
+pub fn neighbor(ip: Ipv4Addr) -> Option<MacAddr> {
+    fs::read_to_string(NEIGHBOR_TABLE)
+        .ok()
+        .and_then(|table| parse_neighbor(&table, ip))
+}
+
+/// Looks `ip` up in a table in the format of `/proc/net/arp`, skipping
+/// entries still waiting for an answer.
+pub fn parse_neighbor(table: &str, ip: Ipv4Addr) -> Option<MacAddr> {
+    table.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let entry_ip: Ipv4Addr = fields.first()?.parse().ok()?;
+        let flags = u16::from_str_radix(fields.get(2)?.trim_start_matches("0x"), 16).ok()?;
+        let mac: MacAddr = fields.get(3)?.parse().ok()?;
+
+        (entry_ip == ip && flags & ATF_COM != 0 && !mac.is_zero()).then_some(mac)
+    })
+}
+
+/// Finds the default gateway `dest` leaves through on the interface
+/// `transport` sends through and resolves its MAC, with ARP or neighbor
+/// solicitation depending on the family of `dest`. The one MAC serves
+/// both families, set gateway.mac in the profile when they are routed
+/// through different routers.
+pub fn gateway_mac(
+    transport: &dyn Transport,
+    limiter: &RateLimiter,
+    dest: IpAddr,
+) -> Result<MacAddr> {
+    let name = &transport.interface().name;
+    let route = default_routes()
+        .into_iter()
+        .find(|x| &x.interface == name && x.gateway.is_ipv4() == dest.is_ipv4())
+        .ok_or_else(|| {
+            ScanError::Interface(format!(
+                "no default {} route through {}, set gateway.mac in the profile",
+                if dest.is_ipv4() { "IPv4" } else { "IPv6" },
+                name
+            ))
+        })?;
+
+    neighbor::resolve(transport, &[route.gateway], limiter)?
+        .remove(&route.gateway)
+        .ok_or_else(|| {
+            ScanError::Interface(format!(
+                "gateway {} does not answer, set gateway.mac in the profile",
+                route.gateway
+            ))
+        })
+}
@@ -1,48 +1,230 @@
 use std::{
-    collections::BTreeSet,
-    net::{IpAddr, Ipv4Addr, SocketAddrV4},
-    sync::atomic::{AtomicBool, Ordering},
+    collections::{BTreeMap, HashMap},
+    fmt,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle, WeakProgressBar};
 use pnet::{
-    datalink::{self, Channel, NetworkInterface},
     packet::{
-        ethernet::EthernetPacket,
-        ip::IpNextHeaderProtocols,
-        ipv4::Ipv4Packet,
+        icmp::{IcmpPacket, IcmpTypes},
+        icmpv6::{Icmpv6Packet, Icmpv6Types},
+        ip::{IpNextHeaderProtocol, IpNextHeaderProtocols},
         tcp::{TcpFlags, TcpPacket},
+        udp::UdpPacket,
         Packet,
     },
     util::MacAddr,
 };
 use rand::Rng;
 
-mod packet;
+use crate::{
+    error::Result,
+    interface::Link,
+    rate_limiter::RateLimiter,
+    timing::RttTable,
+    transport::{Channel, Transport},
+    ScanResult,
+};
+
+use packet::Datagram;
 
-static DONE: AtomicBool = AtomicBool::new(false);
+pub mod connect;
+pub(crate) mod packet;
+mod payloads;
 
-pub fn scan(
-    interface_ip: Ipv4Addr,
-    gateway_mac: MacAddr,
-    socket_addr: Vec<SocketAddrV4>,
-) -> (Vec<SocketAddrV4>, Vec<SocketAddrV4>, Vec<SocketAddrV4>) {
-    println!("{} {}", "💀", "START ICMP DETECTING: ".blue().bold());
+const RECV_TIMEOUT: Duration = Duration::from_millis(10);
+
+/// How often the send thread looks for probes whose timeout expired.
+const RETRANSMIT_TICK: Duration = Duration::from_millis(5);
+
+/// ICMP destination unreachable code for "port unreachable".
+const PORT_UNREACHABLE: u8 = 3;
+
+/// The same for ICMPv6.
+const PORT_UNREACHABLE_V6: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Protocol::Tcp => write!(f, "tcp"),
+            Protocol::Udp => write!(f, "udp"),
+        }
+    }
+}
 
-    let interface = datalink::interfaces()
-        .into_iter()
-        .find(|x| x.ips.first().expect("interface ip error!").ip() == IpAddr::V4(interface_ip))
-        .expect("can not find the interface!!");
+/// The kind of probe sent to TCP ports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScanType {
+    /// Half-open SYN scan.
+    #[default]
+    Syn,
+    /// Bare ACKs, which tell filtered ports from unfiltered ones but not
+    /// open from closed.
+    Ack,
+    /// FIN only. This and the next three rely on RFC 793: a closed port
+    /// resets any segment without SYN, RST or ACK, an open one ignores it.
+    Fin,
+    /// No flags at all.
+    Null,
+    /// FIN, PSH and URG.
+    Xmas,
+    /// FIN and ACK. BSD derived stacks drop it on open ports instead of
+    /// resetting it.
+    Maimon,
+    /// ACKs like `Ack`, but the window of the returned RST tells open ports
+    /// (non-zero) from closed ones on stacks that leak it.
+    Window,
+    /// Full connections through the operating system, no raw access needed.
+    Connect,
+}
 
-    let interface_clone = interface.clone();
-    let gateway_mac_clone = gateway_mac.clone();
+impl ScanType {
+    fn flags(self) -> u8 {
+        match self {
+            ScanType::Syn | ScanType::Connect => TcpFlags::SYN,
+            ScanType::Ack | ScanType::Window => TcpFlags::ACK,
+            ScanType::Fin => TcpFlags::FIN,
+            ScanType::Null => 0,
+            ScanType::Xmas => TcpFlags::FIN | TcpFlags::PSH | TcpFlags::URG,
+            ScanType::Maimon => TcpFlags::FIN | TcpFlags::ACK,
+        }
+    }
 
-    let sockets_btree = get_btree(&socket_addr);
+    /// What a TCP port that never answered is taken to be.
+    fn silent_state(self) -> PortState {
+        match self {
+            ScanType::Syn | ScanType::Ack | ScanType::Window | ScanType::Connect => {
+                PortState::Filtered
+            }
+            ScanType::Fin | ScanType::Null | ScanType::Xmas | ScanType::Maimon => {
+                PortState::OpenFiltered
+            }
+        }
+    }
+}
+
+impl FromStr for ScanType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "syn" => Ok(ScanType::Syn),
+            "ack" => Ok(ScanType::Ack),
+            "fin" => Ok(ScanType::Fin),
+            "null" => Ok(ScanType::Null),
+            "xmas" => Ok(ScanType::Xmas),
+            "maimon" => Ok(ScanType::Maimon),
+            "window" => Ok(ScanType::Window),
+            "connect" => Ok(ScanType::Connect),
+            _ => Err(format!("unknown scan type: {}", s)),
+        }
+    }
+}
 
-    let pb = ProgressBar::new(socket_addr.len() as u64);
+impl fmt::Display for ScanType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ScanType::Syn => "syn",
+            ScanType::Ack => "ack",
+            ScanType::Fin => "fin",
+            ScanType::Null => "null",
+            ScanType::Xmas => "xmas",
+            ScanType::Maimon => "maimon",
+            ScanType::Window => "window",
+            ScanType::Connect => "connect",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What a probed port turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortState {
+    Open,
+    Closed,
+    Filtered,
+    /// Reachable through any firewall, open or closed is unknown (ACK).
+    Unfiltered,
+    /// No answer from a port whose open state is silent too (UDP, FIN…).
+    OpenFiltered,
+}
+
+/// A socket to probe and the protocol to probe it with.
+pub type Target = (Protocol, SocketAddr);
+
+/// A classified socket and how many probes it took to get there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PortResult {
+    pub socket: SocketAddr,
+    pub protocol: Protocol,
+    pub attempts: u8,
+}
+
+struct Probe {
+    attempts: u8,
+    sent_at: Instant,
+}
+
+/// Probes still waiting for an answer and what the answers so far taught
+/// us about round trip times.
+struct State {
+    pending: BTreeMap<Target, Probe>,
+    rtt: RttTable,
+}
+
+type Shared = Arc<Mutex<State>>;
+
+/// A frame that settles one of our probes.
+struct Answer {
+    target: Target,
+    state: PortState,
+    /// The port the probe was sent from.
+    local_port: u16,
+}
+
+/// Probes every target and sorts it by the answer. Frames to a host in
+/// `neighbors` go straight to its MAC, all others through the gateway. The
+/// returned result has no reachable hosts filled in.
+pub fn scan(
+    transport: Arc<dyn Transport>,
+    gateway_mac: Option<MacAddr>,
+    neighbors: HashMap<IpAddr, MacAddr>,
+    scan_type: ScanType,
+    targets: Vec<Target>,
+    rtt: RttTable,
+    limiter: Arc<RateLimiter>,
+) -> Result<ScanResult> {
+    println!("💀 {}", "START SCANNING: ".blue().bold());
+
+    let link = Link::new(transport.interface(), gateway_mac)?.with_neighbors(neighbors);
+    let rx_link = link.clone();
+
+    let rx_channel = transport.open()?;
+    let tx_channel = transport.open()?;
+
+    let state: Shared = Arc::new(Mutex::new(State {
+        pending: BTreeMap::new(),
+        rtt,
+    }));
+    let rx_state = state.clone();
+    let rx_limiter = limiter.clone();
+
+    let pb = ProgressBar::new(targets.len() as u64);
     pb.set_message("SCANNING");
     pb.set_style(
         ProgressStyle::with_template(
@@ -54,132 +236,332 @@ pub fn scan(
     let rx_pb = pb.downgrade();
     let tx_pb = pb.downgrade();
 
-    let rx_thread = thread::spawn(move || receive(interface, gateway_mac, sockets_btree, rx_pb));
+    let done = Arc::new(AtomicBool::new(false));
+    let rx_done = done.clone();
+
+    let rx_thread = thread::spawn(move || {
+        receive(
+            rx_channel, rx_link, scan_type, rx_state, rx_limiter, rx_done, rx_pb,
+        )
+    });
 
     let tx_thread = thread::spawn(move || {
-        send(interface_clone, gateway_mac_clone, socket_addr, tx_pb);
+        let res = send(tx_channel, link, scan_type, targets, state, limiter, tx_pb);
+        done.store(true, Ordering::SeqCst);
+        res
     });
 
-    let (open_ports, closed_ports, filtered_ports) =
-        rx_thread.join().expect("receive thread error!");
-    let _ = tx_thread.join().expect("send thread error");
+    let send_res = tx_thread.join().expect("send thread error");
+    let result = rx_thread.join().expect("receive thread error!")?;
+    send_res?;
 
     pb.finish_with_message("💀 SCANNING DONE");
 
-    (open_ports, closed_ports, filtered_ports)
-}
-
-fn get_btree(target_sockets: &[SocketAddrV4]) -> BTreeSet<SocketAddrV4> {
-    target_sockets.iter().map(|x| *x).collect()
+    Ok(result)
 }
 
+/// Sweeps every socket once, then keeps re-probing each silent socket
+/// whenever the timeout of its host expires, until it answers or runs out
+/// of retries.
 fn send(
-    interface: NetworkInterface,
-    gateway_mac: MacAddr,
-    target_sockets: Vec<SocketAddrV4>,
+    mut tx: Box<dyn Channel>,
+    link: Link,
+    scan_type: ScanType,
+    targets: Vec<Target>,
+    state: Shared,
+    limiter: Arc<RateLimiter>,
     pb: WeakProgressBar,
-) {
-    let (mut tx, _) = match datalink::channel(&interface, Default::default()) {
-        Ok(Channel::Ethernet(tx, rx)) => (tx, rx),
-        Ok(_) => panic!("Unknown channel type!"),
-        Err(e) => panic!("Error happened: {}", e),
-    };
-
-    let IpAddr::V4(src_ip) = interface.ips.first().expect("interface ip error!").ip() else {
-        panic!();
-    };
-
-    for dest_socket in target_sockets {
-        let src_port = rand::thread_rng().gen_range(20000..=65535);
-
-        let packet_syn = packet::build(
-            interface.mac.expect("interface MAC error!"),
-            SocketAddrV4::new(src_ip, src_port),
-            dest_socket,
-            gateway_mac,
-            TcpFlags::SYN,
+) -> Result<()> {
+    for target in targets {
+        limiter.acquire();
+
+        state.lock().unwrap().pending.insert(
+            target,
+            Probe {
+                attempts: 1,
+                sent_at: Instant::now(),
+            },
         );
 
-        tx.send_to(&packet_syn, None).unwrap().unwrap();
+        send_probe(tx.as_mut(), &link, scan_type, target)?;
         pb.upgrade().unwrap().inc(1);
 
         thread::sleep(Duration::from_micros(1));
     }
 
-    thread::sleep(Duration::from_millis(100));
+    loop {
+        let (due, waiting) = {
+            let mut state = state.lock().unwrap();
+            let State { pending, rtt } = &mut *state;
+            let attempts = rtt.timing().attempts();
+            let now = Instant::now();
+
+            let mut due = Vec::new();
+            let mut waiting = false;
+
+            for (target, probe) in pending.iter_mut() {
+                if now - probe.sent_at < rtt.timeout(target.1.ip()) {
+                    waiting = true;
+                } else if probe.attempts < attempts {
+                    probe.attempts += 1;
+                    probe.sent_at = now;
+                    due.push(*target);
+                    waiting = true;
+                }
+            }
+
+            (due, waiting)
+        };
+
+        if !waiting {
+            break;
+        }
+
+        for target in due {
+            limiter.acquire();
+            send_probe(tx.as_mut(), &link, scan_type, target)?;
+        }
+
+        thread::sleep(RETRANSMIT_TICK);
+    }
+
+    Ok(())
+}
+
+fn send_probe(
+    tx: &mut dyn Channel,
+    link: &Link,
+    scan_type: ScanType,
+    (protocol, dest_socket): Target,
+) -> Result<()> {
+    let src_socket = SocketAddr::new(
+        link.source(dest_socket.ip())?,
+        rand::thread_rng().gen_range(20000..=65535),
+    );
 
-    DONE.store(true, Ordering::SeqCst);
+    match protocol {
+        Protocol::Tcp => tx.send(&packet::build(
+            link.src_mac,
+            src_socket,
+            dest_socket,
+            link.next_hop(dest_socket.ip())?,
+            scan_type.flags(),
+        )),
+        Protocol::Udp => tx.send(&packet::build_udp(
+            link.src_mac,
+            src_socket,
+            dest_socket,
+            link.next_hop(dest_socket.ip())?,
+            payloads::udp(dest_socket.port()),
+        )),
+    }
 }
 
 fn receive(
-    interface: NetworkInterface,
-    gateway_mac: MacAddr,
-    mut target_sockets: BTreeSet<SocketAddrV4>,
+    mut channel: Box<dyn Channel>,
+    link: Link,
+    scan_type: ScanType,
+    state: Shared,
+    limiter: Arc<RateLimiter>,
+    done: Arc<AtomicBool>,
     pb: WeakProgressBar,
-) -> (Vec<SocketAddrV4>, Vec<SocketAddrV4>, Vec<SocketAddrV4>) {
-    let IpAddr::V4(src_ip) = interface.ips.first().expect("interface ip error!").ip() else {
-        panic!();
-    };
-
-    let Ok(Channel::Ethernet(mut tx, mut rx)) = datalink::channel(&interface, Default::default())
-    else {
-        panic!()
-    };
+) -> Result<ScanResult> {
+    let mut result = ScanResult::default();
+
+    while !done.load(Ordering::SeqCst) {
+        let Some(frame) = channel.recv(RECV_TIMEOUT)? else {
+            continue;
+        };
+        let Some(datagram) = packet::parse(frame) else {
+            continue;
+        };
+        if !link.is_local(datagram.destination) {
+            continue;
+        }
 
-    let mut open_ports = Vec::new();
-    let mut filtered_ports = Vec::new();
-    let mut closed_ports = Vec::new();
+        let answer = match datagram.protocol {
+            IpNextHeaderProtocols::Tcp => tcp_answer(&datagram, scan_type),
+            IpNextHeaderProtocols::Udp => udp_answer(&datagram),
+            IpNextHeaderProtocols::Icmp => icmp_answer(&datagram, &link),
+            IpNextHeaderProtocols::Icmpv6 => icmpv6_answer(&datagram, &link),
+            _ => None,
+        };
+        let Some(Answer {
+            target,
+            state: port_state,
+            local_port,
+        }) = answer
+        else {
+            continue;
+        };
+        let (protocol, target_socket) = target;
+
+        let attempts = {
+            let mut state = state.lock().unwrap();
+            let Some(probe) = state.pending.remove(&target) else {
+                continue;
+            };
+
+            // Karn's algorithm: an answer to a retransmitted probe could
+            // belong to any of its copies, so it says nothing about the RTT
+            if probe.attempts == 1 {
+                state
+                    .rtt
+                    .sample(target_socket.ip(), probe.sent_at.elapsed());
+            }
 
-    loop {
-        let eth_packet = EthernetPacket::new(rx.next().unwrap()).unwrap();
-
-        let ipv4_packet = Ipv4Packet::new(eth_packet.payload()).unwrap();
-
-        if ipv4_packet.get_next_level_protocol() == IpNextHeaderProtocols::Tcp
-            && ipv4_packet.get_destination() == src_ip
-        {
-            let tcp_packet = TcpPacket::new(ipv4_packet.payload()).unwrap();
-
-            let target_ip = ipv4_packet.get_source();
-            let target_port = tcp_packet.get_source();
-            let target_socket = SocketAddrV4::new(target_ip, target_port);
-
-            if target_sockets.contains(&target_socket) {
-                let tcp_flags = tcp_packet.get_flags();
-
-                if is_ack_syn(tcp_flags) {
-                    pb.upgrade().unwrap().println(format!(
-                        "   {} {}",
-                        "OPEN".green().bold(),
-                        target_socket
-                    ));
-                    open_ports.push(target_socket);
-                    target_sockets.remove(&target_socket);
-
-                    let packet_rst = packet::build(
-                        interface.mac.unwrap(),
-                        SocketAddrV4::new(src_ip, tcp_packet.get_destination()),
-                        target_socket,
-                        gateway_mac,
-                        TcpFlags::RST,
-                    );
-
-                    tx.send_to(&packet_rst, None).unwrap().unwrap();
-                } else if is_rst(tcp_flags) {
-                    closed_ports.push(target_socket);
-                    target_sockets.remove(&target_socket);
-                }
+            probe.attempts
+        };
+
+        if port_state == PortState::Open {
+            pb.upgrade().unwrap().println(format!(
+                "   {} {}/{}",
+                "OPEN".green().bold(),
+                target_socket,
+                protocol
+            ));
+
+            if protocol == Protocol::Tcp && scan_type == ScanType::Syn {
+                let packet_rst = packet::build(
+                    link.src_mac,
+                    SocketAddr::new(datagram.destination, local_port),
+                    target_socket,
+                    link.next_hop(target_socket.ip())?,
+                    TcpFlags::RST,
+                );
+
+                limiter.acquire();
+                channel.send(&packet_rst)?;
             }
         }
 
-        if DONE.load(Ordering::SeqCst) {
-            break;
+        result.push(
+            port_state,
+            PortResult {
+                socket: target_socket,
+                protocol,
+                attempts,
+            },
+        );
+    }
+
+    for (&(protocol, socket), probe) in state.lock().unwrap().pending.iter() {
+        // an open UDP port may simply ignore the payload we sent
+        let silent = match protocol {
+            Protocol::Tcp => scan_type.silent_state(),
+            Protocol::Udp => PortState::OpenFiltered,
+        };
+        result.push(
+            silent,
+            PortResult {
+                socket,
+                protocol,
+                attempts: probe.attempts,
+            },
+        );
+    }
+
+    Ok(result)
+}
+
+/// For SYN probes a SYN-ACK means open and an RST closed. ACK probes are
+/// reset by open and closed ports alike, so an RST only proves that the
+/// probe got through, unless the window of the RST gives the port away.
+/// The stealth scans only ever get resets from closed ports.
+fn tcp_answer(datagram: &Datagram, scan_type: ScanType) -> Option<Answer> {
+    let tcp_packet = TcpPacket::new(datagram.payload)?;
+    let tcp_flags = tcp_packet.get_flags();
+
+    let state = match scan_type {
+        ScanType::Syn if is_ack_syn(tcp_flags) => PortState::Open,
+        ScanType::Syn if is_rst(tcp_flags) => PortState::Closed,
+        ScanType::Ack if is_rst(tcp_flags) => PortState::Unfiltered,
+        ScanType::Window if is_rst(tcp_flags) && tcp_packet.get_window() != 0 => PortState::Open,
+        ScanType::Window if is_rst(tcp_flags) => PortState::Closed,
+        ScanType::Fin | ScanType::Null | ScanType::Xmas | ScanType::Maimon if is_rst(tcp_flags) => {
+            PortState::Closed
         }
+        _ => return None,
+    };
+
+    Some(Answer {
+        target: (
+            Protocol::Tcp,
+            SocketAddr::new(datagram.source, tcp_packet.get_source()),
+        ),
+        state,
+        local_port: tcp_packet.get_destination(),
+    })
+}
+
+/// Any datagram back from the probed port means it is open.
+fn udp_answer(datagram: &Datagram) -> Option<Answer> {
+    let udp_packet = UdpPacket::new(datagram.payload)?;
+
+    Some(Answer {
+        target: (
+            Protocol::Udp,
+            SocketAddr::new(datagram.source, udp_packet.get_source()),
+        ),
+        state: PortState::Open,
+        local_port: udp_packet.get_destination(),
+    })
+}
+
+fn icmp_answer(datagram: &Datagram, link: &Link) -> Option<Answer> {
+    let icmp_packet = IcmpPacket::new(datagram.payload)?;
+    if icmp_packet.get_icmp_type() != IcmpTypes::DestinationUnreachable {
+        return None;
+    }
+
+    unreachable_answer(
+        icmp_packet.payload(),
+        icmp_packet.get_icmp_code().0 == PORT_UNREACHABLE,
+        link,
+    )
+}
+
+fn icmpv6_answer(datagram: &Datagram, link: &Link) -> Option<Answer> {
+    let icmp_packet = Icmpv6Packet::new(datagram.payload)?;
+    if icmp_packet.get_icmpv6_type() != Icmpv6Types::DestinationUnreachable {
+        return None;
     }
 
-    filtered_ports.append(&mut target_sockets.into_iter().collect::<Vec<SocketAddrV4>>());
+    unreachable_answer(
+        icmp_packet.payload(),
+        icmp_packet.get_icmpv6_code().0 == PORT_UNREACHABLE_V6,
+        link,
+    )
+}
+
+/// Destination unreachable quoting one of our probes. Port unreachable is
+/// the closed answer of UDP; every other code means something in the way
+/// dropped the probe.
+fn unreachable_answer(body: &[u8], port_unreachable: bool, link: &Link) -> Option<Answer> {
+    // the quoted datagram follows 4 unused bytes, in ICMP and ICMPv6 alike
+    let inner = packet::parse_ip(body.get(4..)?)?;
+    if !link.is_local(inner.source) || inner.payload.len() < 4 {
+        return None;
+    }
+    let local_port = u16::from_be_bytes([inner.payload[0], inner.payload[1]]);
+    let dest_port = u16::from_be_bytes([inner.payload[2], inner.payload[3]]);
+
+    let protocol = match inner.protocol {
+        IpNextHeaderProtocols::Tcp => Protocol::Tcp,
+        IpNextHeaderProtocols::Udp => Protocol::Udp,
+        IpNextHeaderProtocol(_) => return None,
+    };
+    let state = if protocol == Protocol::Udp && port_unreachable {
+        PortState::Closed
+    } else {
+        PortState::Filtered
+    };
 
-    (open_ports, closed_ports, filtered_ports)
+    Some(Answer {
+        target: (protocol, SocketAddr::new(inner.destination, dest_port)),
+        state,
+        local_port,
+    })
 }
 
 fn is_ack_syn(tcp_flags: u8) -> bool {
@@ -0,0 +1,260 @@
+//! Scanning through ordinary sockets, for when raw access is not available.
+//!
+//! TCP ports are probed with a non-blocking connect(); UDP ports with a
+//! connected datagram socket, which the kernel lets us know got an ICMP
+//! port unreachable back. One poll loop waits on all of them.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, ErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::Instant,
+};
+
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+use mio::{
+    event::{Event, Source},
+    net::{TcpStream, UdpSocket},
+    Events, Interest, Poll, Token,
+};
+
+use super::{payloads, PortResult, PortState, Protocol, Target};
+use crate::{error::Result, rate_limiter::RateLimiter, timing::RttTable, ScanResult};
+
+/// How many connections may be in flight at once.
+pub const MAX_CONNECTIONS: usize = 256;
+
+/// A probe waiting for its answer.
+struct Connection {
+    target: Target,
+    attempts: u8,
+    started: Instant,
+    deadline: Instant,
+    socket: Socket,
+}
+
+enum Socket {
+    Tcp(TcpStream),
+    Udp(UdpSocket),
+}
+
+impl Socket {
+    fn source(&mut self) -> &mut dyn Source {
+        match self {
+            Socket::Tcp(stream) => stream,
+            Socket::Udp(socket) => socket,
+        }
+    }
+}
+
+/// Probes every target with at most `MAX_CONNECTIONS` sockets open at a
+/// time. The returned result has no reachable hosts filled in.
+pub fn scan(
+    targets: Vec<Target>,
+    mut rtt: RttTable,
+    limiter: Arc<RateLimiter>,
+) -> Result<ScanResult> {
+    println!("💀 {}", "START CONNECT SCANNING: ".blue().bold());
+
+    let pb = ProgressBar::new(targets.len() as u64);
+    pb.set_message("SCANNING");
+    pb.set_style(
+        ProgressStyle::with_template(
+            "{spinner:.cyan/blue}{msg:.blue} [{elapsed}] [{bar:50.cyan/blue}]) [{pos}/{len}]",
+        )
+        .unwrap()
+        .progress_chars("#>-"),
+    );
+
+    let max_attempts = rtt.timing().attempts();
+    let mut queue: VecDeque<(Target, u8)> = targets.into_iter().map(|x| (x, 1)).collect();
+    let mut pending: HashMap<Token, Connection> = HashMap::new();
+    let mut next_token = 0;
+
+    let mut poll = Poll::new()?;
+    let mut events = Events::with_capacity(MAX_CONNECTIONS);
+    let mut result = ScanResult::default();
+
+    let mut settle = |(protocol, socket): Target, state: PortState, attempts: u8| {
+        if state == PortState::Open {
+            pb.println(format!(
+                "   {} {}/{}",
+                "OPEN".green().bold(),
+                socket,
+                protocol
+            ));
+        }
+        result.push(
+            state,
+            PortResult {
+                socket,
+                protocol,
+                attempts,
+            },
+        );
+        pb.inc(1);
+    };
+
+    while !queue.is_empty() || !pending.is_empty() {
+        while pending.len() < MAX_CONNECTIONS {
+            let Some((target, attempts)) = queue.pop_front() else {
+                break;
+            };
+            limiter.acquire();
+
+            let started = Instant::now();
+            let mut socket = match open(target) {
+                Ok(socket) => socket,
+                Err(e) => {
+                    settle(
+                        target,
+                        classify(target.0, e).unwrap_or(silent(target.0)),
+                        attempts,
+                    );
+                    continue;
+                }
+            };
+
+            let token = Token(next_token);
+            next_token = next_token.wrapping_add(1);
+            poll.registry().register(
+                socket.source(),
+                token,
+                Interest::READABLE | Interest::WRITABLE,
+            )?;
+
+            pending.insert(
+                token,
+                Connection {
+                    target,
+                    attempts,
+                    started,
+                    deadline: started + rtt.timeout(target.1.ip()),
+                    socket,
+                },
+            );
+        }
+
+        // every socket left to open failed at once, nothing to wait for
+        if pending.is_empty() {
+            continue;
+        }
+
+        let timeout = pending
+            .values()
+            .map(|x| x.deadline.saturating_duration_since(Instant::now()))
+            .min();
+        if let Err(e) = poll.poll(&mut events, timeout) {
+            if e.kind() == ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(e.into());
+        }
+
+        for event in events.iter() {
+            let Some(connection) = pending.get(&event.token()) else {
+                continue;
+            };
+            let Some(state) = answer(connection, event) else {
+                continue;
+            };
+
+            let mut connection = pending.remove(&event.token()).unwrap();
+            poll.registry().deregister(connection.socket.source())?;
+            // every attempt is a fresh socket, so any answer times its own probe
+            rtt.sample(connection.target.1.ip(), connection.started.elapsed());
+            settle(connection.target, state, connection.attempts);
+        }
+
+        let now = Instant::now();
+        let expired: Vec<Token> = pending
+            .iter()
+            .filter(|(_, x)| x.deadline <= now)
+            .map(|(token, _)| *token)
+            .collect();
+
+        for token in expired {
+            let mut connection = pending.remove(&token).unwrap();
+            poll.registry().deregister(connection.socket.source())?;
+
+            if connection.attempts < max_attempts {
+                queue.push_back((connection.target, connection.attempts + 1));
+            } else {
+                let target = connection.target;
+                settle(target, silent(target.0), connection.attempts);
+            }
+        }
+    }
+
+    pb.finish_with_message("💀 SCANNING DONE");
+
+    Ok(result)
+}
+
+/// Starts a probe without waiting for it to complete.
+fn open((protocol, socket): Target) -> io::Result<Socket> {
+    match protocol {
+        Protocol::Tcp => TcpStream::connect(socket).map(Socket::Tcp),
+        Protocol::Udp => {
+            let unspecified = match socket {
+                SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            };
+            let udp_socket = UdpSocket::bind(SocketAddr::new(unspecified, 0))?;
+            udp_socket.connect(socket)?;
+            udp_socket.send(payloads::udp(socket.port()))?;
+            Ok(Socket::Udp(udp_socket))
+        }
+    }
+}
+
+/// What a readiness event says about the port, `None` while the probe is
+/// still waiting for its answer.
+fn answer(connection: &Connection, event: &Event) -> Option<PortState> {
+    let protocol = connection.target.0;
+
+    match &connection.socket {
+        Socket::Tcp(stream) => {
+            if !event.is_writable() && !event.is_error() {
+                return None;
+            }
+            if let Some(e) = stream.take_error().unwrap_or_else(Some) {
+                return classify(protocol, e);
+            }
+            match stream.peer_addr() {
+                Ok(_) => Some(PortState::Open),
+                Err(e) if e.kind() == ErrorKind::NotConnected => None,
+                Err(e) => classify(protocol, e),
+            }
+        }
+        Socket::Udp(socket) => {
+            let mut buf = [0_u8; 512];
+            match socket.recv(&mut buf) {
+                Ok(_) => Some(PortState::Open),
+                Err(e) => classify(protocol, e),
+            }
+        }
+    }
+}
+
+/// Maps what the kernel made of a probe onto a port state, `None` when it
+/// has heard nothing back yet. Errors that are not an answer from the port
+/// itself leave it filtered rather than ending the scan.
+fn classify(protocol: Protocol, e: io::Error) -> Option<PortState> {
+    match e.kind() {
+        ErrorKind::ConnectionRefused | ErrorKind::ConnectionReset => Some(PortState::Closed),
+        ErrorKind::WouldBlock | ErrorKind::Interrupted => None,
+        ErrorKind::TimedOut => Some(silent(protocol)),
+        _ => Some(PortState::Filtered),
+    }
+}
+
+/// The state of a port that never answered.
+fn silent(protocol: Protocol) -> PortState {
+    match protocol {
+        Protocol::Tcp => PortState::Filtered,
+        Protocol::Udp => PortState::OpenFiltered,
+    }
+}
@@ -1,26 +1,88 @@
-use std::net::SocketAddrV4;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use pnet::{
     packet::{
-        ethernet::{EtherTypes, MutableEthernetPacket},
-        ip::IpNextHeaderProtocols,
-        ipv4::{checksum, Ipv4Flags, MutableIpv4Packet},
-        tcp::{ipv4_checksum, MutableTcpPacket, TcpOption},
+        ethernet::{EtherType, EtherTypes, EthernetPacket, MutableEthernetPacket},
+        ip::{IpNextHeaderProtocol, IpNextHeaderProtocols},
+        ipv4::{checksum, Ipv4Flags, Ipv4Packet, MutableIpv4Packet},
+        ipv6::{Ipv6Packet, MutableIpv6Packet},
+        tcp::{self, MutableTcpPacket, TcpOption},
+        udp::{self, MutableUdpPacket},
     },
     util::MacAddr,
 };
 
-use crate::config::{ETHERNET_HEADER_LEN, IPV4_HEADER_LEN};
+use crate::config::{ETHERNET_HEADER_LEN, IPV4_HEADER_LEN, IPV6_HEADER_LEN, UDP_HEADER_LEN};
+
+/// TCP header with the MSS, window scale and SACK options of a SYN.
+const TCP_HEADER_LEN: usize = 32;
+
+/// The addresses and transport payload of an IPv4 or IPv6 frame.
+pub struct Datagram<'a> {
+    pub source: IpAddr,
+    pub destination: IpAddr,
+    pub protocol: IpNextHeaderProtocol,
+    /// The whole IP packet, header included. Only the simulated network
+    /// quotes it back.
+    #[cfg_attr(not(feature = "simnet"), allow(dead_code))]
+    pub packet: &'a [u8],
+    pub payload: &'a [u8],
+}
+
+/// Splits an ethernet frame carrying IPv4 or IPv6.
+pub fn parse(frame: &[u8]) -> Option<Datagram<'_>> {
+    let ethertype = EthernetPacket::new(frame)?.get_ethertype();
+    if ethertype != EtherTypes::Ipv4 && ethertype != EtherTypes::Ipv6 {
+        return None;
+    }
+
+    parse_ip(frame.get(ETHERNET_HEADER_LEN..)?)
+}
+
+/// Reads an IPv4 or IPv6 packet, possibly truncated like the ones quoted
+/// by ICMP errors. Extension headers are not followed, their payload shows
+/// up with the extension as protocol.
+pub fn parse_ip(packet: &[u8]) -> Option<Datagram<'_>> {
+    match packet.first()? >> 4 {
+        4 => {
+            let ipv4_packet = Ipv4Packet::new(packet)?;
+            let header_len = ipv4_packet.get_header_length() as usize * 4;
+            let total_len = (ipv4_packet.get_total_length() as usize).min(packet.len());
+
+            Some(Datagram {
+                source: IpAddr::V4(ipv4_packet.get_source()),
+                destination: IpAddr::V4(ipv4_packet.get_destination()),
+                protocol: ipv4_packet.get_next_level_protocol(),
+                packet: &packet[..total_len],
+                payload: packet.get(header_len..total_len)?,
+            })
+        }
+        6 => {
+            let ipv6_packet = Ipv6Packet::new(packet)?;
+            let total_len =
+                (IPV6_HEADER_LEN + ipv6_packet.get_payload_length() as usize).min(packet.len());
+
+            Some(Datagram {
+                source: IpAddr::V6(ipv6_packet.get_source()),
+                destination: IpAddr::V6(ipv6_packet.get_destination()),
+                protocol: ipv6_packet.get_next_header(),
+                packet: &packet[..total_len],
+                payload: &packet[IPV6_HEADER_LEN..total_len],
+            })
+        }
+        _ => None,
+    }
+}
 
 pub fn build(
     src_mac: MacAddr,
-    src_socket: SocketAddrV4,
-    dest_socket: SocketAddrV4,
-    gateway_mac: MacAddr,
+    src_socket: SocketAddr,
+    dest_socket: SocketAddr,
+    dest_mac: MacAddr,
     flags: u8,
-) -> [u8; ETHERNET_HEADER_LEN + IPV4_HEADER_LEN + 32] {
-    let mut packet_buf = [0_u8; ETHERNET_HEADER_LEN + IPV4_HEADER_LEN + 32];
-    let mut tcp_header = MutableTcpPacket::new(&mut packet_buf[34..]).unwrap();
+) -> Vec<u8> {
+    let mut tcp_buf = [0_u8; TCP_HEADER_LEN];
+    let mut tcp_header = MutableTcpPacket::new(&mut tcp_buf).unwrap();
 
     tcp_header.set_source(src_socket.port());
     tcp_header.set_destination(dest_socket.port());
@@ -38,35 +100,141 @@ pub fn build(
         TcpOption::nop(),
         TcpOption::sack_perm(),
     ]);
-    tcp_header.set_checksum(ipv4_checksum(
-        &tcp_header.to_immutable(),
+    tcp_header.set_checksum(match (src_socket.ip(), dest_socket.ip()) {
+        (IpAddr::V4(src), IpAddr::V4(dest)) => {
+            tcp::ipv4_checksum(&tcp_header.to_immutable(), &src, &dest)
+        }
+        (IpAddr::V6(src), IpAddr::V6(dest)) => {
+            tcp::ipv6_checksum(&tcp_header.to_immutable(), &src, &dest)
+        }
+        _ => unreachable!("{} and {} differ in family", src_socket, dest_socket),
+    });
+
+    ip_frame(
+        src_mac,
+        dest_mac,
         src_socket.ip(),
         dest_socket.ip(),
-    ));
+        IpNextHeaderProtocols::Tcp,
+        &tcp_buf,
+    )
+}
 
-    let mut ip_header = MutableIpv4Packet::new(
-        &mut packet_buf[ETHERNET_HEADER_LEN..(ETHERNET_HEADER_LEN + IPV4_HEADER_LEN)],
+pub fn build_udp(
+    src_mac: MacAddr,
+    src_socket: SocketAddr,
+    dest_socket: SocketAddr,
+    dest_mac: MacAddr,
+    payload: &[u8],
+) -> Vec<u8> {
+    let udp_len = UDP_HEADER_LEN + payload.len();
+    let mut udp_buf = vec![0_u8; udp_len];
+    let mut udp_header = MutableUdpPacket::new(&mut udp_buf).unwrap();
+
+    udp_header.set_source(src_socket.port());
+    udp_header.set_destination(dest_socket.port());
+    udp_header.set_length(udp_len as u16);
+    udp_header.set_payload(payload);
+    udp_header.set_checksum(match (src_socket.ip(), dest_socket.ip()) {
+        (IpAddr::V4(src), IpAddr::V4(dest)) => {
+            udp::ipv4_checksum(&udp_header.to_immutable(), &src, &dest)
+        }
+        (IpAddr::V6(src), IpAddr::V6(dest)) => {
+            udp::ipv6_checksum(&udp_header.to_immutable(), &src, &dest)
+        }
+        _ => unreachable!("{} and {} differ in family", src_socket, dest_socket),
+    });
+
+    ip_frame(
+        src_mac,
+        dest_mac,
+        src_socket.ip(),
+        dest_socket.ip(),
+        IpNextHeaderProtocols::Udp,
+        &udp_buf,
     )
-    .unwrap();
+}
+
+/// Wraps `payload` into an IP packet of the family of the two addresses
+/// and that into an ethernet frame.
+pub fn ip_frame(
+    src_mac: MacAddr,
+    dest_mac: MacAddr,
+    src_ip: IpAddr,
+    dest_ip: IpAddr,
+    protocol: IpNextHeaderProtocol,
+    payload: &[u8],
+) -> Vec<u8> {
+    match (src_ip, dest_ip) {
+        (IpAddr::V4(src), IpAddr::V4(dest)) => ethernet(
+            src_mac,
+            dest_mac,
+            EtherTypes::Ipv4,
+            &ipv4(src, dest, protocol, payload),
+        ),
+        (IpAddr::V6(src), IpAddr::V6(dest)) => ethernet(
+            src_mac,
+            dest_mac,
+            EtherTypes::Ipv6,
+            &ipv6(src, dest, protocol, payload),
+        ),
+        _ => unreachable!("{} and {} differ in family", src_ip, dest_ip),
+    }
+}
+
+fn ipv4(src: Ipv4Addr, dest: Ipv4Addr, protocol: IpNextHeaderProtocol, payload: &[u8]) -> Vec<u8> {
+    let mut packet_buf = vec![0_u8; IPV4_HEADER_LEN + payload.len()];
+    packet_buf[IPV4_HEADER_LEN..].copy_from_slice(payload);
+
+    let mut ip_header = MutableIpv4Packet::new(&mut packet_buf).unwrap();
     ip_header.set_version(4);
     ip_header.set_header_length(5);
     ip_header.set_dscp(0);
     ip_header.set_ecn(0);
-    ip_header.set_total_length(52);
+    ip_header.set_total_length((IPV4_HEADER_LEN + payload.len()) as u16);
     ip_header.set_identification(rand::random());
     ip_header.set_flags(Ipv4Flags::DontFragment);
     ip_header.set_fragment_offset(0);
     ip_header.set_ttl(128);
-    ip_header.set_next_level_protocol(IpNextHeaderProtocols::Tcp);
-    ip_header.set_source(src_socket.ip().clone());
-    ip_header.set_destination(dest_socket.ip().clone());
+    ip_header.set_next_level_protocol(protocol);
+    ip_header.set_source(src);
+    ip_header.set_destination(dest);
     ip_header.set_checksum(checksum(&ip_header.to_immutable()));
 
+    packet_buf
+}
+
+fn ipv6(src: Ipv6Addr, dest: Ipv6Addr, protocol: IpNextHeaderProtocol, payload: &[u8]) -> Vec<u8> {
+    let mut packet_buf = vec![0_u8; IPV6_HEADER_LEN + payload.len()];
+    packet_buf[IPV6_HEADER_LEN..].copy_from_slice(payload);
+
+    let mut ip_header = MutableIpv6Packet::new(&mut packet_buf).unwrap();
+    ip_header.set_version(6);
+    ip_header.set_traffic_class(0);
+    ip_header.set_flow_label(0);
+    ip_header.set_payload_length(payload.len() as u16);
+    ip_header.set_next_header(protocol);
+    // neighbor discovery messages are only accepted at 255 (RFC 4861)
+    ip_header.set_hop_limit(if protocol == IpNextHeaderProtocols::Icmpv6 {
+        255
+    } else {
+        128
+    });
+    ip_header.set_source(src);
+    ip_header.set_destination(dest);
+
+    packet_buf
+}
+
+fn ethernet(src_mac: MacAddr, dest_mac: MacAddr, ethertype: EtherType, payload: &[u8]) -> Vec<u8> {
+    let mut packet_buf = vec![0_u8; ETHERNET_HEADER_LEN + payload.len()];
+    packet_buf[ETHERNET_HEADER_LEN..].copy_from_slice(payload);
+
     let mut eth_header =
-        MutableEthernetPacket::new(&mut packet_buf[0..ETHERNET_HEADER_LEN]).unwrap();
-    eth_header.set_destination(gateway_mac);
+        MutableEthernetPacket::new(&mut packet_buf[..ETHERNET_HEADER_LEN]).unwrap();
+    eth_header.set_destination(dest_mac);
     eth_header.set_source(src_mac);
-    eth_header.set_ethertype(EtherTypes::Ipv4);
+    eth_header.set_ethertype(ethertype);
 
     packet_buf
 }
@@ -0,0 +1,55 @@
+/// DNS query for the root NS records, recursion desired.
+const DNS: &[u8] = &[
+    0x13, 0x37, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00,
+    0x01,
+];
+
+/// NTPv4 client request.
+const NTP: &[u8] = &[
+    0xe3, 0x00, 0x04, 0xfa, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// SNMPv1 get-request for sysDescr.0 with community "public".
+const SNMP: &[u8] = &[
+    0x30, 0x29, 0x02, 0x01, 0x00, 0x04, 0x06, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0xa0, 0x1c, 0x02,
+    0x04, 0x13, 0x37, 0x13, 0x37, 0x02, 0x01, 0x00, 0x02, 0x01, 0x00, 0x30, 0x0e, 0x30, 0x0c, 0x06,
+    0x08, 0x2b, 0x06, 0x01, 0x02, 0x01, 0x01, 0x01, 0x00, 0x05, 0x00,
+];
+
+/// NetBIOS node status request for the wildcard name.
+const NETBIOS_NS: &[u8] = &[
+    0x13, 0x37, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20, 0x43, 0x4b, 0x41,
+    0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
+    0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x00, 0x00, 0x21,
+    0x00, 0x01,
+];
+
+/// SSDP discovery of every device.
+const SSDP: &[u8] = b"M-SEARCH * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\nMAN: \"ssdp:discover\"\r\nMX: 1\r\nST: ssdp:all\r\n\r\n";
+
+/// Portmapper (sunrpc) NULL call.
+const RPCBIND: &[u8] = &[
+    0x13, 0x37, 0x13, 0x37, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x86, 0xa0,
+    0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// TFTP read request for "a" in octet mode.
+const TFTP: &[u8] = b"\x00\x01a\x00octet\x00";
+
+/// A payload the service usually listening on `port` answers to; empty
+/// datagrams are sent everywhere else.
+pub fn udp(port: u16) -> &'static [u8] {
+    match port {
+        53 | 5353 => DNS,
+        69 => TFTP,
+        111 => RPCBIND,
+        123 => NTP,
+        137 => NETBIOS_NS,
+        161 => SNMP,
+        1900 => SSDP,
+        _ => &[],
+    }
+}
@@ -0,0 +1,459 @@
+//! A simulated network of hosts for end-to-end tests.
+//!
+//! `VirtualNetwork` implements `Transport`, so it can be handed to
+//! `Scanner::with_transport` and answers probes the way real hosts would,
+//! optionally losing and reordering frames on the way.
+
+mod host;
+mod packet;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::{IpAddr, Ipv4Addr},
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+use pnet::{
+    datalink::NetworkInterface,
+    ipnetwork::{IpNetwork, Ipv4Network},
+    packet::{
+        arp::ArpOperations,
+        ethernet::EthernetPacket,
+        icmp::{IcmpPacket, IcmpTypes},
+        icmpv6::{ndp::NeighborSolicitPacket, Icmpv6Packet, Icmpv6Types},
+        ip::IpNextHeaderProtocols,
+        tcp::{TcpFlags, TcpPacket},
+        udp::UdpPacket,
+    },
+    util::MacAddr,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    arp::{self, Arp},
+    error::Result,
+    scanner::{self, packet::Datagram},
+    transport::{Channel, Transport},
+};
+
+use packet::Reply;
+
+const REORDER_WINDOW: usize = 8;
+
+const PORT_UNREACHABLE: u8 = 3;
+
+const PORT_UNREACHABLE_V6: u8 = 4;
+
+pub use host::{EchoBehavior, PortBehavior, VirtualHost};
+
+/// Builds an interface description suitable for a `VirtualNetwork`.
+pub fn interface(mac: MacAddr, ip: Ipv4Addr, prefix: u8) -> NetworkInterface {
+    NetworkInterface {
+        name: "sim0".into(),
+        description: "simulated network".into(),
+        index: 0,
+        mac: Some(mac),
+        ips: vec![IpNetwork::V4(
+            Ipv4Network::new(ip, prefix).expect("invalid prefix"),
+        )],
+        flags: 0,
+    }
+}
+
+pub struct VirtualNetwork {
+    interface: NetworkInterface,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    gateway_mac: MacAddr,
+    networks: Vec<IpNetwork>,
+    state: Mutex<State>,
+    inboxes: Mutex<Vec<Arc<Inbox>>>,
+}
+
+struct State {
+    hosts: BTreeMap<IpAddr, VirtualHost>,
+    loss: f64,
+    reorder: bool,
+    rng: StdRng,
+    limits: HashMap<IpAddr, (Instant, u32)>,
+    held: Vec<Vec<u8>>,
+    probes: usize,
+}
+
+#[derive(Default)]
+struct Inbox {
+    frames: Mutex<Vec<Vec<u8>>>,
+    ready: Condvar,
+}
+
+impl VirtualNetwork {
+    /// A network reached through `interface`. Hosts in one of its subnets
+    /// answer from their own MAC, every other host sits behind the router
+    /// at `gateway_mac`.
+    pub fn new(interface: NetworkInterface, gateway_mac: MacAddr) -> Self {
+        VirtualNetwork {
+            shared: Arc::new(Shared {
+                gateway_mac,
+                networks: interface.ips.clone(),
+                state: Mutex::new(State {
+                    hosts: BTreeMap::new(),
+                    loss: 0.0,
+                    reorder: false,
+                    rng: StdRng::seed_from_u64(0),
+                    limits: HashMap::new(),
+                    held: Vec::new(),
+                    probes: 0,
+                }),
+                inboxes: Mutex::new(Vec::new()),
+            }),
+            interface,
+        }
+    }
+
+    pub fn host(self, host: VirtualHost) -> Self {
+        self.shared
+            .state
+            .lock()
+            .unwrap()
+            .hosts
+            .insert(host.ip, host);
+        self
+    }
+
+    /// Drops each probe and each reply independently with probability `loss`.
+    pub fn loss(self, loss: f64) -> Self {
+        self.shared.state.lock().unwrap().loss = loss;
+        self
+    }
+
+    /// Holds back up to `REORDER_WINDOW` replies and releases them in random
+    /// order instead of as soon as they are produced.
+    pub fn reorder(self, reorder: bool) -> Self {
+        self.shared.state.lock().unwrap().reorder = reorder;
+        self
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        self.shared.state.lock().unwrap().rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// How many frames have been sent into the network so far.
+    pub fn probes(&self) -> usize {
+        self.shared.state.lock().unwrap().probes
+    }
+}
+
+impl Transport for VirtualNetwork {
+    fn interface(&self) -> &NetworkInterface {
+        &self.interface
+    }
+
+    fn open(&self) -> Result<Box<dyn Channel>> {
+        let inbox = Arc::new(Inbox::default());
+        self.shared.inboxes.lock().unwrap().push(inbox.clone());
+
+        Ok(Box::new(VirtualChannel {
+            shared: self.shared.clone(),
+            inbox,
+            buf: Vec::new(),
+        }))
+    }
+}
+
+impl Shared {
+    fn respond(&self, frame: &[u8]) -> Vec<Vec<u8>> {
+        let mut state = self.state.lock().unwrap();
+        state.probes += 1;
+
+        let reply = match arp::parse(frame) {
+            Some(request) => self.answer_arp(&mut state, &request),
+            None => self.answer_ip(&mut state, frame),
+        };
+        let Some(reply) = reply else {
+            return vec![];
+        };
+
+        if !state.reorder {
+            return vec![reply];
+        }
+
+        state.held.push(reply);
+        let mut released = Vec::new();
+        while state.held.len() > REORDER_WINDOW {
+            let held = state.held.len();
+            let index = state.rng.gen_range(0..held);
+            released.push(state.held.swap_remove(index));
+        }
+        released
+    }
+
+    /// Hosts on our subnet answer who-has for their own address, whether
+    /// or not they answer pings.
+    fn answer_arp(&self, state: &mut State, request: &Arp) -> Option<Vec<u8>> {
+        let target = IpAddr::V4(request.target_ip);
+        if request.operation != ArpOperations::Request || !self.is_on_link(target) {
+            return None;
+        }
+        let host = state.hosts.get(&target)?.clone();
+
+        let loss = state.loss;
+        if state.rng.gen_bool(loss) || state.rng.gen_bool(loss) {
+            return None;
+        }
+
+        Some(arp::reply(
+            host.mac,
+            request.target_ip,
+            request.sender_mac,
+            request.sender_ip,
+        ))
+    }
+
+    fn answer_ip(&self, state: &mut State, frame: &[u8]) -> Option<Vec<u8>> {
+        let eth = EthernetPacket::new(frame)?;
+        let request = scanner::packet::parse(frame)?;
+
+        // a neighbor solicitation goes to a multicast group and names the
+        // host it is looking for, which only answers from our own subnet
+        let solicited = Some(request.payload)
+            .filter(|_| request.protocol == IpNextHeaderProtocols::Icmpv6)
+            .and_then(NeighborSolicitPacket::new)
+            .filter(|x| x.get_icmpv6_type() == Icmpv6Types::NeighborSolicit)
+            .map(|x| IpAddr::V6(x.get_target_addr()));
+        if solicited.is_some_and(|x| !self.is_on_link(x)) {
+            return None;
+        }
+
+        let host_ip = solicited.unwrap_or(request.destination);
+        let host = state.hosts.get(&host_ip)?.clone();
+
+        // a host on our subnet only sees frames sent to its own MAC, any
+        // other host only the ones sent through the gateway
+        let host_mac = if self.is_on_link(host.ip) {
+            host.mac
+        } else {
+            self.gateway_mac
+        };
+        if solicited.is_none() && eth.get_destination() != host_mac {
+            return None;
+        }
+
+        let loss = state.loss;
+        if state.rng.gen_bool(loss) {
+            return None;
+        }
+
+        let reply =
+            match request.protocol {
+                IpNextHeaderProtocols::Icmp => IcmpPacket::new(request.payload)
+                    .filter(|x| x.get_icmp_type() == IcmpTypes::EchoRequest)
+                    .and_then(|x| match host.echo {
+                        EchoBehavior::Reply => Some(packet::echo_reply(&x)),
+                        EchoBehavior::Silent => None,
+                    }),
+
+                IpNextHeaderProtocols::Icmpv6 => Icmpv6Packet::new(request.payload).and_then(|x| {
+                    match (x.get_icmpv6_type(), host.echo, host.ip, request.source) {
+                        (Icmpv6Types::EchoRequest, EchoBehavior::Reply, ..) => {
+                            Some(packet::echo_reply_v6(&request, &x))
+                        }
+                        (Icmpv6Types::NeighborSolicit, _, IpAddr::V6(target), IpAddr::V6(dest)) => {
+                            Some(packet::neighbor_advert(target, host.mac, dest))
+                        }
+                        _ => None,
+                    }
+                }),
+
+                IpNextHeaderProtocols::Tcp => match TcpPacket::new(request.payload) {
+                    Some(tcp) if tcp.get_flags() & TcpFlags::RST == 0 => {
+                        let behavior = host.port_behavior(tcp.get_destination());
+                        state.answer(behavior, &request, &|x| {
+                            answer_tcp(x, &host, &request, &tcp)
+                        })
+                    }
+                    _ => None,
+                },
+
+                IpNextHeaderProtocols::Udp => UdpPacket::new(request.payload).and_then(|udp| {
+                    let behavior = host.udp_port_behavior(udp.get_destination());
+                    state.answer(behavior, &request, &|x| answer_udp(x, &request, &udp))
+                }),
+
+                _ => None,
+            };
+
+        match reply {
+            Some((protocol, payload)) if !state.rng.gen_bool(loss) => {
+                Some(scanner::packet::ip_frame(
+                    host_mac,
+                    eth.get_source(),
+                    host.ip,
+                    request.source,
+                    protocol,
+                    &payload,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_on_link(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|x| x.contains(ip))
+    }
+
+    /// Releases every held-back reply, in random order.
+    fn flush(&self) {
+        let released = {
+            let mut state = self.state.lock().unwrap();
+            let mut held = std::mem::take(&mut state.held);
+            let mut released = Vec::with_capacity(held.len());
+            while !held.is_empty() {
+                let index = state.rng.gen_range(0..held.len());
+                released.push(held.swap_remove(index));
+            }
+            released
+        };
+
+        for frame in released {
+            self.deliver(frame);
+        }
+    }
+
+    fn deliver(&self, frame: Vec<u8>) {
+        for inbox in self.inboxes.lock().unwrap().iter() {
+            inbox.frames.lock().unwrap().push(frame.clone());
+            inbox.ready.notify_all();
+        }
+    }
+}
+
+impl State {
+    /// Applies the rate limiting and loss wrapped around a behaviour, then
+    /// lets `respond` answer like the innermost one.
+    fn answer(
+        &mut self,
+        behavior: &PortBehavior,
+        request: &Datagram,
+        respond: &dyn Fn(&PortBehavior) -> Option<Reply>,
+    ) -> Option<Reply> {
+        match behavior {
+            PortBehavior::RateLimited {
+                inner,
+                burst,
+                window,
+            } => {
+                let now = Instant::now();
+                let entry = self.limits.entry(request.destination).or_insert((now, 0));
+
+                if now.duration_since(entry.0) >= *window {
+                    *entry = (now, 0);
+                }
+                if entry.1 >= *burst {
+                    return None;
+                }
+                entry.1 += 1;
+
+                self.answer(inner, request, respond)
+            }
+            PortBehavior::Lossy { inner, loss } => {
+                if self.rng.gen_bool(*loss) {
+                    None
+                } else {
+                    self.answer(inner, request, respond)
+                }
+            }
+            _ => respond(behavior),
+        }
+    }
+}
+
+fn answer_tcp(
+    behavior: &PortBehavior,
+    host: &VirtualHost,
+    request: &Datagram,
+    tcp: &TcpPacket,
+) -> Option<Reply> {
+    let flags = tcp.get_flags();
+
+    match behavior {
+        PortBehavior::Open if flags & TcpFlags::SYN != 0 && flags & TcpFlags::ACK == 0 => Some(
+            packet::tcp_reply(request, tcp, TcpFlags::SYN | TcpFlags::ACK, 65535),
+        ),
+        // like BSD derived stacks, which is what a Maimon scan relies on
+        PortBehavior::Open if flags & TcpFlags::FIN != 0 => None,
+        PortBehavior::Open if flags & TcpFlags::ACK != 0 => Some(packet::tcp_reply(
+            request,
+            tcp,
+            TcpFlags::RST,
+            host.rst_window,
+        )),
+        PortBehavior::Closed if flags & TcpFlags::ACK != 0 => {
+            Some(packet::tcp_reply(request, tcp, TcpFlags::RST, 0))
+        }
+        PortBehavior::Closed => Some(packet::tcp_reply(
+            request,
+            tcp,
+            TcpFlags::RST | TcpFlags::ACK,
+            0,
+        )),
+        PortBehavior::Unreachable(code) => Some(packet::unreachable(request, *code)),
+        _ => None,
+    }
+}
+
+fn answer_udp(behavior: &PortBehavior, request: &Datagram, udp: &UdpPacket) -> Option<Reply> {
+    let port_unreachable = match request.source {
+        IpAddr::V4(_) => PORT_UNREACHABLE,
+        IpAddr::V6(_) => PORT_UNREACHABLE_V6,
+    };
+
+    match behavior {
+        PortBehavior::Open => Some(packet::udp_reply(request, udp)),
+        PortBehavior::Closed => Some(packet::unreachable(request, port_unreachable)),
+        PortBehavior::Unreachable(code) => Some(packet::unreachable(request, *code)),
+        _ => None,
+    }
+}
+
+struct VirtualChannel {
+    shared: Arc<Shared>,
+    inbox: Arc<Inbox>,
+    buf: Vec<u8>,
+}
+
+impl Channel for VirtualChannel {
+    fn send(&mut self, frame: &[u8]) -> Result<()> {
+        for reply in self.shared.respond(frame) {
+            self.shared.deliver(reply);
+        }
+
+        Ok(())
+    }
+
+    fn recv(&mut self, timeout: Duration) -> Result<Option<&[u8]>> {
+        let frames = self.inbox.frames.lock().unwrap();
+        let (mut frames, _) = self
+            .inbox
+            .ready
+            .wait_timeout_while(frames, timeout, |x| x.is_empty())
+            .unwrap();
+
+        if frames.is_empty() {
+            // the line went quiet, let whatever was held back through
+            drop(frames);
+            self.shared.flush();
+            frames = self.inbox.frames.lock().unwrap();
+        }
+
+        if frames.is_empty() {
+            return Ok(None);
+        }
+
+        self.buf = frames.remove(0);
+        Ok(Some(&self.buf))
+    }
+}
@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, net::IpAddr, time::Duration};
+
+use pnet::util::MacAddr;
+
+/// How a simulated host reacts to probes sent to one of its ports.
+#[derive(Debug, Clone)]
+pub enum PortBehavior {
+    /// A listening service. UDP services echo the datagram back.
+    Open,
+    /// Nothing listens: TCP probes are reset, UDP probes get ICMP port
+    /// unreachable.
+    Closed,
+    /// Probes are silently discarded.
+    Dropped,
+    /// A firewall answers with ICMP destination unreachable and this code,
+    /// ICMPv6 for IPv6 hosts.
+    Unreachable(u8),
+    /// Answers like `inner`, but to at most `burst` probes per `window`,
+    /// counted across every rate limited port of the host.
+    RateLimited {
+        inner: Box<PortBehavior>,
+        burst: u32,
+        window: Duration,
+    },
+    /// Answers like `inner`, losing each answer with probability `loss`.
+    Lossy { inner: Box<PortBehavior>, loss: f64 },
+}
+
+impl PortBehavior {
+    pub fn rate_limited(inner: PortBehavior, burst: u32, window: Duration) -> Self {
+        PortBehavior::RateLimited {
+            inner: Box::new(inner),
+            burst,
+            window,
+        }
+    }
+
+    pub fn lossy(inner: PortBehavior, loss: f64) -> Self {
+        PortBehavior::Lossy {
+            inner: Box::new(inner),
+            loss,
+        }
+    }
+}
+
+/// How a simulated host reacts to ICMP echo requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EchoBehavior {
+    Reply,
+    Silent,
+}
+
+#[derive(Debug, Clone)]
+pub struct VirtualHost {
+    pub ip: IpAddr,
+    pub mac: MacAddr,
+    pub echo: EchoBehavior,
+    pub ports: BTreeMap<u16, PortBehavior>,
+    pub default_port: PortBehavior,
+    pub udp_ports: BTreeMap<u16, PortBehavior>,
+    pub default_udp_port: PortBehavior,
+    pub rst_window: u16,
+}
+
+impl VirtualHost {
+    /// A host that answers pings and closes every TCP and UDP port. Its MAC
+    /// is made up from the low bytes of `ip`.
+    pub fn new(ip: impl Into<IpAddr>) -> Self {
+        let ip = ip.into();
+        let low = match ip {
+            IpAddr::V4(ip) => ip.octets(),
+            IpAddr::V6(ip) => {
+                let octets = ip.octets();
+                [octets[12], octets[13], octets[14], octets[15]]
+            }
+        };
+
+        VirtualHost {
+            ip,
+            mac: MacAddr(0x02, 0x53, low[0], low[1], low[2], low[3]),
+            echo: EchoBehavior::Reply,
+            ports: BTreeMap::new(),
+            default_port: PortBehavior::Closed,
+            udp_ports: BTreeMap::new(),
+            default_udp_port: PortBehavior::Closed,
+            rst_window: 0,
+        }
+    }
+
+    /// Hardware address the host answers neighbor discovery with.
+    pub fn mac(mut self, mac: MacAddr) -> Self {
+        self.mac = mac;
+        self
+    }
+
+    pub fn echo(mut self, echo: EchoBehavior) -> Self {
+        self.echo = echo;
+        self
+    }
+
+    pub fn port(mut self, port: u16, behavior: PortBehavior) -> Self {
+        self.ports.insert(port, behavior);
+        self
+    }
+
+    /// Behaviour of every port not configured through `port`.
+    pub fn default_port(mut self, behavior: PortBehavior) -> Self {
+        self.default_port = behavior;
+        self
+    }
+
+    pub fn port_behavior(&self, port: u16) -> &PortBehavior {
+        self.ports.get(&port).unwrap_or(&self.default_port)
+    }
+
+    /// Window advertised in the RSTs open ports send back to ACK probes.
+    /// Real stacks differ here, most of them send 0.
+    pub fn rst_window(mut self, window: u16) -> Self {
+        self.rst_window = window;
+        self
+    }
+
+    pub fn udp_port(mut self, port: u16, behavior: PortBehavior) -> Self {
+        self.udp_ports.insert(port, behavior);
+        self
+    }
+
+    /// Behaviour of every UDP port not configured through `udp_port`.
+    pub fn default_udp_port(mut self, behavior: PortBehavior) -> Self {
+        self.default_udp_port = behavior;
+        self
+    }
+
+    pub fn udp_port_behavior(&self, port: u16) -> &PortBehavior {
+        self.udp_ports.get(&port).unwrap_or(&self.default_udp_port)
+    }
+}
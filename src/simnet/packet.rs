@@ -0,0 +1,155 @@
+//! Transport payloads of the replies simulated hosts send. `Shared::respond`
+//! wraps them into IP packets and frames.
+
+use std::net::{IpAddr, Ipv6Addr};
+
+use pnet::packet::{
+    icmp::{self, IcmpCode, IcmpPacket, IcmpTypes, MutableIcmpPacket},
+    icmpv6::{
+        self,
+        ndp::{MutableNeighborAdvertPacket, NdpOptionTypes, NeighborAdvertFlags},
+        Icmpv6Code, Icmpv6Packet, Icmpv6Types, MutableIcmpv6Packet,
+    },
+    ip::{IpNextHeaderProtocol, IpNextHeaderProtocols},
+    tcp::{self, MutableTcpPacket, TcpPacket},
+    udp::{self, MutableUdpPacket, UdpPacket},
+    Packet,
+};
+
+use crate::{
+    config::{IPV4_HEADER_LEN, UDP_HEADER_LEN},
+    scanner::packet::Datagram,
+};
+
+use pnet::util::MacAddr;
+
+const TCP_HEADER_LEN: usize = 20;
+
+const ICMP_HEADER_LEN: usize = 8;
+
+const NEIGHBOR_ADVERT_LEN: usize = 32;
+
+/// A reply: the protocol it is carried as and its transport payload.
+pub type Reply = (IpNextHeaderProtocol, Vec<u8>);
+
+pub fn tcp_reply(request: &Datagram, probe: &TcpPacket, flags: u8, window: u16) -> Reply {
+    let mut tcp_buf = vec![0_u8; TCP_HEADER_LEN];
+    let mut tcp_header = MutableTcpPacket::new(&mut tcp_buf).unwrap();
+
+    tcp_header.set_source(probe.get_destination());
+    tcp_header.set_destination(probe.get_source());
+    tcp_header.set_sequence(rand::random());
+    tcp_header.set_acknowledgement(probe.get_sequence().wrapping_add(1));
+    tcp_header.set_data_offset(5);
+    tcp_header.set_flags(flags);
+    tcp_header.set_window(window);
+    tcp_header.set_checksum(match (request.destination, request.source) {
+        (IpAddr::V4(src), IpAddr::V4(dest)) => {
+            tcp::ipv4_checksum(&tcp_header.to_immutable(), &src, &dest)
+        }
+        (IpAddr::V6(src), IpAddr::V6(dest)) => {
+            tcp::ipv6_checksum(&tcp_header.to_immutable(), &src, &dest)
+        }
+        _ => unreachable!(),
+    });
+
+    (IpNextHeaderProtocols::Tcp, tcp_buf)
+}
+
+/// A datagram from the probed port carrying the probe's payload back.
+pub fn udp_reply(request: &Datagram, probe: &UdpPacket) -> Reply {
+    let mut udp_buf = vec![0_u8; UDP_HEADER_LEN + probe.payload().len()];
+    let mut udp_header = MutableUdpPacket::new(&mut udp_buf).unwrap();
+
+    udp_header.set_source(probe.get_destination());
+    udp_header.set_destination(probe.get_source());
+    udp_header.set_length((UDP_HEADER_LEN + probe.payload().len()) as u16);
+    udp_header.set_payload(probe.payload());
+    udp_header.set_checksum(match (request.destination, request.source) {
+        (IpAddr::V4(src), IpAddr::V4(dest)) => {
+            udp::ipv4_checksum(&udp_header.to_immutable(), &src, &dest)
+        }
+        (IpAddr::V6(src), IpAddr::V6(dest)) => {
+            udp::ipv6_checksum(&udp_header.to_immutable(), &src, &dest)
+        }
+        _ => unreachable!(),
+    });
+
+    (IpNextHeaderProtocols::Udp, udp_buf)
+}
+
+pub fn echo_reply(request: &IcmpPacket) -> Reply {
+    let mut icmp_buf = request.packet().to_vec();
+    let mut icmp_packet = MutableIcmpPacket::new(&mut icmp_buf).unwrap();
+    icmp_packet.set_icmp_type(IcmpTypes::EchoReply);
+    icmp_packet.set_checksum(0);
+    icmp_packet.set_checksum(icmp::checksum(&icmp_packet.to_immutable()));
+
+    (IpNextHeaderProtocols::Icmp, icmp_buf)
+}
+
+pub fn echo_reply_v6(request: &Datagram, probe: &Icmpv6Packet) -> Reply {
+    let mut icmp_buf = probe.packet().to_vec();
+    let mut icmp_packet = MutableIcmpv6Packet::new(&mut icmp_buf).unwrap();
+    icmp_packet.set_icmpv6_type(Icmpv6Types::EchoReply);
+    icmp_packet.set_checksum(0);
+    if let (IpAddr::V6(src), IpAddr::V6(dest)) = (request.destination, request.source) {
+        icmp_packet.set_checksum(icmpv6::checksum(&icmp_packet.to_immutable(), &src, &dest));
+    }
+
+    (IpNextHeaderProtocols::Icmpv6, icmp_buf)
+}
+
+/// A solicited neighbor advertisement for `target`, sent from it to
+/// `dest` and carrying `mac` as the target link-layer address.
+pub fn neighbor_advert(target: Ipv6Addr, mac: MacAddr, dest: Ipv6Addr) -> Reply {
+    let mut icmp_buf = vec![0_u8; NEIGHBOR_ADVERT_LEN];
+    let mut icmp_packet = MutableNeighborAdvertPacket::new(&mut icmp_buf).unwrap();
+    icmp_packet.set_icmpv6_type(Icmpv6Types::NeighborAdvert);
+    icmp_packet.set_flags(NeighborAdvertFlags::Solicited | NeighborAdvertFlags::Override);
+    icmp_packet.set_target_addr(target);
+
+    // target link-layer address option, one 8 byte unit
+    icmp_buf[24] = NdpOptionTypes::TargetLLAddr.0;
+    icmp_buf[25] = 1;
+    icmp_buf[26..32].copy_from_slice(&mac.octets());
+
+    let checksum = icmpv6::checksum(&Icmpv6Packet::new(&icmp_buf).unwrap(), &target, &dest);
+    MutableIcmpv6Packet::new(&mut icmp_buf)
+        .unwrap()
+        .set_checksum(checksum);
+
+    (IpNextHeaderProtocols::Icmpv6, icmp_buf)
+}
+
+/// Destination unreachable with `code`, quoting the header and first 8
+/// bytes of an IPv4 `request` or all of an IPv6 one.
+pub fn unreachable(request: &Datagram, code: u8) -> Reply {
+    let quoted = match request.source {
+        IpAddr::V4(_) => &request.packet[..(IPV4_HEADER_LEN + 8).min(request.packet.len())],
+        IpAddr::V6(_) => request.packet,
+    };
+
+    let mut icmp_buf = vec![0_u8; ICMP_HEADER_LEN + quoted.len()];
+    icmp_buf[ICMP_HEADER_LEN..].copy_from_slice(quoted);
+
+    match (request.destination, request.source) {
+        (IpAddr::V4(_), IpAddr::V4(_)) => {
+            let mut icmp_packet = MutableIcmpPacket::new(&mut icmp_buf).unwrap();
+            icmp_packet.set_icmp_type(IcmpTypes::DestinationUnreachable);
+            icmp_packet.set_icmp_code(IcmpCode::new(code));
+            icmp_packet.set_checksum(icmp::checksum(&icmp_packet.to_immutable()));
+
+            (IpNextHeaderProtocols::Icmp, icmp_buf)
+        }
+        (IpAddr::V6(src), IpAddr::V6(dest)) => {
+            let mut icmp_packet = MutableIcmpv6Packet::new(&mut icmp_buf).unwrap();
+            icmp_packet.set_icmpv6_type(Icmpv6Types::DestinationUnreachable);
+            icmp_packet.set_icmpv6_code(Icmpv6Code::new(code));
+            icmp_packet.set_checksum(icmpv6::checksum(&icmp_packet.to_immutable(), &src, &dest));
+
+            (IpNextHeaderProtocols::Icmpv6, icmp_buf)
+        }
+        _ => unreachable!(),
+    }
+}
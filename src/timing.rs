@@ -0,0 +1,215 @@
+use std::{collections::HashMap, fmt, net::IpAddr, str::FromStr, time::Duration};
+
+pub const DEFAULT_RETRIES: u8 = 2;
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(100);
+
+pub const DEFAULT_MIN_TIMEOUT: Duration = Duration::from_millis(20);
+
+pub const DEFAULT_MAX_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How persistently unanswered probes are chased.
+#[derive(Debug, Clone, Copy)]
+pub struct Timing {
+    /// Extra probes sent to a socket that stayed silent.
+    pub retries: u8,
+    /// How long to wait for an answer before any round trip was measured.
+    pub timeout: Duration,
+    /// Lower bound of the timeout derived from measured round trips.
+    pub min_timeout: Duration,
+    /// Upper bound of the timeout derived from measured round trips.
+    pub max_timeout: Duration,
+    /// Packets per second over all probes of a scan, `None` for no limit.
+    pub rate: Option<f64>,
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Timing {
+            retries: DEFAULT_RETRIES,
+            timeout: DEFAULT_TIMEOUT,
+            min_timeout: DEFAULT_MIN_TIMEOUT,
+            max_timeout: DEFAULT_MAX_TIMEOUT,
+            rate: None,
+        }
+    }
+}
+
+impl Timing {
+    /// Probes sent to a silent socket at most, one plus the retries but
+    /// never more than an attempt counter holds.
+    pub fn attempts(&self) -> u8 {
+        self.retries.saturating_add(1)
+    }
+}
+
+/// Named presets for `Timing`, from the slowest and stealthiest to the
+/// fastest, in the spirit of nmap's `-T0` … `-T5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingTemplate {
+    Paranoid,
+    Sneaky,
+    Polite,
+    Normal,
+    Aggressive,
+    Insane,
+}
+
+impl TimingTemplate {
+    pub fn timing(self) -> Timing {
+        let ms = Duration::from_millis;
+
+        match self {
+            TimingTemplate::Paranoid => Timing {
+                retries: 5,
+                timeout: Duration::from_secs(300),
+                min_timeout: ms(100),
+                max_timeout: Duration::from_secs(10),
+                rate: Some(1.0 / 300.0),
+            },
+            TimingTemplate::Sneaky => Timing {
+                retries: 5,
+                timeout: Duration::from_secs(15),
+                min_timeout: ms(100),
+                max_timeout: Duration::from_secs(10),
+                rate: Some(1.0 / 15.0),
+            },
+            TimingTemplate::Polite => Timing {
+                retries: 5,
+                timeout: Duration::from_secs(1),
+                min_timeout: ms(100),
+                max_timeout: Duration::from_secs(10),
+                rate: Some(2.5),
+            },
+            TimingTemplate::Normal => Timing::default(),
+            TimingTemplate::Aggressive => Timing {
+                retries: 1,
+                timeout: ms(50),
+                min_timeout: ms(10),
+                max_timeout: ms(1250),
+                rate: None,
+            },
+            TimingTemplate::Insane => Timing {
+                retries: 0,
+                timeout: ms(25),
+                min_timeout: ms(5),
+                max_timeout: ms(300),
+                rate: None,
+            },
+        }
+    }
+}
+
+impl FromStr for TimingTemplate {
+    type Err = String;
+
+    /// Accepts the template names as well as `0` … `5` and `T0` … `T5`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+        // only a single `t` and only in front of a number
+        let name = match name.strip_prefix('t') {
+            Some(level) if level.bytes().all(|x| x.is_ascii_digit()) => level,
+            _ => &name,
+        };
+
+        match name {
+            "paranoid" | "0" => Ok(TimingTemplate::Paranoid),
+            "sneaky" | "1" => Ok(TimingTemplate::Sneaky),
+            "polite" | "2" => Ok(TimingTemplate::Polite),
+            "normal" | "3" => Ok(TimingTemplate::Normal),
+            "aggressive" | "4" => Ok(TimingTemplate::Aggressive),
+            "insane" | "5" => Ok(TimingTemplate::Insane),
+            _ => Err(format!("unknown timing template: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for TimingTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TimingTemplate::Paranoid => "paranoid",
+            TimingTemplate::Sneaky => "sneaky",
+            TimingTemplate::Polite => "polite",
+            TimingTemplate::Normal => "normal",
+            TimingTemplate::Aggressive => "aggressive",
+            TimingTemplate::Insane => "insane",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Smoothed round trip time and its variance, as TCP keeps them (RFC 6298).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RttEstimator {
+    srtt: Option<Duration>,
+    rttvar: Duration,
+}
+
+impl RttEstimator {
+    pub fn sample(&mut self, rtt: Duration) {
+        match self.srtt {
+            None => {
+                self.srtt = Some(rtt);
+                self.rttvar = rtt / 2;
+            }
+            Some(srtt) => {
+                let delta = srtt.abs_diff(rtt);
+                self.rttvar = (self.rttvar * 3 + delta) / 4;
+                self.srtt = Some((srtt * 7 + rtt) / 8);
+            }
+        }
+    }
+
+    pub fn srtt(&self) -> Option<Duration> {
+        self.srtt
+    }
+
+    /// `srtt + 4 * rttvar` clamped to the bounds of `timing`, or the
+    /// initial timeout while nothing was measured.
+    pub fn timeout(&self, timing: &Timing) -> Duration {
+        match self.srtt {
+            Some(srtt) => (srtt + self.rttvar * 4).clamp(timing.min_timeout, timing.max_timeout),
+            None => timing.timeout,
+        }
+    }
+}
+
+/// Round trip estimates per host, falling back to the estimate over all
+/// hosts for the ones that never answered.
+#[derive(Debug, Clone)]
+pub struct RttTable {
+    timing: Timing,
+    global: RttEstimator,
+    hosts: HashMap<IpAddr, RttEstimator>,
+}
+
+impl RttTable {
+    pub fn new(timing: Timing) -> Self {
+        RttTable {
+            timing,
+            global: RttEstimator::default(),
+            hosts: HashMap::new(),
+        }
+    }
+
+    pub fn timing(&self) -> &Timing {
+        &self.timing
+    }
+
+    pub fn sample(&mut self, host: IpAddr, rtt: Duration) {
+        self.global.sample(rtt);
+        self.hosts.entry(host).or_default().sample(rtt);
+    }
+
+    pub fn srtt(&self, host: IpAddr) -> Option<Duration> {
+        self.hosts.get(&host).and_then(RttEstimator::srtt)
+    }
+
+    /// How long to wait for `host` before retransmitting or giving up.
+    pub fn timeout(&self, host: IpAddr) -> Duration {
+        self.hosts
+            .get(&host)
+            .unwrap_or(&self.global)
+            .timeout(&self.timing)
+    }
+}
@@ -1,47 +1,63 @@
-use std::{collections::BTreeSet, fs, net::Ipv4Addr, path::Path};
-
-use ipnet::Ipv4AddrRange;
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs,
+    net::{IpAddr, ToSocketAddrs},
+    path::Path,
+    time::Duration,
+};
+
+use ipnet::{IpNet, Ipv4AddrRange, Ipv6AddrRange};
 use pnet::util::MacAddr;
 use toml::{Table, Value};
 
-use crate::config;
+use crate::{
+    config::Services,
+    error::{Result, ScanError},
+    Discovery, PortSpec, Protocol, ScanConfig, ScanType, Selector, TargetSpec, Timing,
+    TimingTemplate,
+};
+
+/// Ranges and prefixes are expanded into every address they hold, refuse
+/// anything bigger than an IPv4 /16 or an IPv6 /112.
+const MAX_RANGE_HOSTS: u128 = 1 << 16;
 
 pub struct Profile {
-    pub interface_ip: Ipv4Addr,
-    pub gateway_mac: MacAddr,
-    pub ip_vec: Vec<Ipv4Addr>,
-    pub ports_vec: Vec<u16>,
-    pub show_open: bool,
-    pub show_closed: bool,
-    pub show_filtered: bool,
+    pub scan: ScanConfig,
+    pub show: ShowRule,
+    /// Names and ranks ports, the embedded table unless the profile sets
+    /// `services`.
+    pub services: Services,
+    /// What each target leaves out, kept for ports given later on.
+    exclude: Vec<Exclude>,
 }
 impl Profile {
-    fn new(
-        interface_ip: Ipv4Addr,
-        gateway_mac: MacAddr,
-        ip_vec: Vec<Ipv4Addr>,
-        ports_vec: Vec<u16>,
-        show_open: bool,
-        show_closed: bool,
-        show_filtered: bool,
-    ) -> Self {
+    fn new(scan: ScanConfig, show: ShowRule, services: Services, exclude: Vec<Exclude>) -> Self {
         Profile {
-            interface_ip,
-            gateway_mac,
-            ip_vec,
-            ports_vec,
-            show_open,
-            show_closed,
-            show_filtered,
+            scan,
+            show,
+            services,
+            exclude,
+        }
+    }
+
+    /// Replaces the ports of every target, unprefixed ones are TCP. Ports
+    /// the profile excludes stay excluded.
+    pub fn set_ports(&mut self, ports: &PortSpec) {
+        for (target, exclude) in self.scan.targets.iter_mut().zip(&self.exclude) {
+            let (tcp, udp) = ports.clone().split(Protocol::Tcp);
+            target.ports = exclude.filter(Protocol::Tcp, tcp);
+            target.udp_ports = exclude.filter(Protocol::Udp, udp);
         }
     }
 }
 
 #[derive(Debug)]
-struct ShowRule {
-    open: bool,
-    closed: bool,
-    filtered: bool,
+pub struct ShowRule {
+    pub open: bool,
+    pub closed: bool,
+    pub filtered: bool,
+    pub unfiltered: bool,
+    pub open_filtered: bool,
 }
 impl Default for ShowRule {
     fn default() -> Self {
@@ -49,137 +65,532 @@ impl Default for ShowRule {
             open: true,
             closed: false,
             filtered: false,
+            unfiltered: true,
+            open_filtered: false,
         }
     }
 }
 
-pub fn parse(path: impl AsRef<Path>) -> Profile {
-    let table: Table = fs::read_to_string(path).unwrap().parse().unwrap();
+pub fn parse(path: impl AsRef<Path>) -> Result<Profile> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).map_err(|e| {
+        ScanError::Config(format!("can not read profile {}: {}", path.display(), e))
+    })?;
 
-    let (interface_ip, gateway_mac, show_rules) = parse_profile(&table);
+    parse_str(&content)
+}
+
+pub fn parse_str(content: &str) -> Result<Profile> {
+    let table: Table = content.parse()?;
 
-    let (ip_vec, ports_vec) = parse_targets(&table);
+    let (interface, gateway_mac, scan_type, discovery, timing, show_rules) = parse_profile(&table)?;
+
+    let services = match table.get("profile").and_then(|x| x.get("services")) {
+        Some(path) => Services::load(as_str(path)?)?,
+        None => Services::embedded(),
+    };
 
-    Profile::new(
-        interface_ip,
+    let (targets, exclude) = parse_targets(&table, &services)?;
+
+    let scan = ScanConfig {
+        interface,
         gateway_mac,
-        ip_vec,
-        ports_vec,
-        show_rules.open,
-        show_rules.closed,
-        show_rules.filtered,
-    )
+        targets,
+        scan_type,
+        discovery,
+        skip_discovery: parse_switch(&table["profile"], "skip_discovery")?,
+        discovery_only: parse_switch(&table["profile"], "discovery_only")?,
+        timing,
+    };
+
+    Ok(Profile::new(scan, show_rules, services, exclude))
 }
 
-fn parse_profile(table: &Table) -> (Ipv4Addr, MacAddr, ShowRule) {
-    let profile = table.get("profile").unwrap();
+type ProfileSettings = (
+    Selector,
+    Option<MacAddr>,
+    ScanType,
+    Discovery,
+    Timing,
+    ShowRule,
+);
+
+fn parse_profile(table: &Table) -> Result<ProfileSettings> {
+    let profile = table
+        .get("profile")
+        .ok_or_else(|| config_error("missing [profile] table"))?;
+
+    let interface = parse_interface(profile)?;
+    let gateway_mac = match profile.get("gateway").and_then(|x| x.get("mac")) {
+        Some(mac) => Some(as_str(mac)?),
+        None => None,
+    };
+
+    let scan_type = match profile.get("scan_type") {
+        Some(scan_type) => as_str(scan_type)?.parse().map_err(config_error)?,
+        None => ScanType::default(),
+    };
 
-    let Value::String(interface_ip) = profile.get("interface").unwrap().get("ip").unwrap() else {
-        panic!("can not find interface ip");
+    let discovery = match profile.get("discovery") {
+        Some(discovery) => parse_discovery(discovery)?,
+        None => Discovery::default(),
     };
-    let Value::String(gateway_mac) = profile.get("gateway").unwrap().get("mac").unwrap() else {
-        panic!("can not find gateway mac");
+
+    let mut timing = match profile.get("timing") {
+        Some(template) => parse_template(template)?.timing(),
+        None => Timing::default(),
     };
 
+    if let Some(retries) = profile.get("retries") {
+        timing.retries = retries
+            .as_integer()
+            .and_then(|x| u8::try_from(x).ok())
+            .ok_or_else(|| config_error(format!("invalid retries: {}", retries)))?;
+    }
+
+    if let Some(timeout) = profile.get("timeout") {
+        timing.timeout = parse_millis(timeout)?;
+    }
+
+    if let Some(min_timeout) = profile.get("min_timeout") {
+        timing.min_timeout = parse_millis(min_timeout)?;
+    }
+
+    if let Some(max_timeout) = profile.get("max_timeout") {
+        timing.max_timeout = parse_millis(max_timeout)?;
+    }
+
+    if let Some(rate) = profile.get("rate") {
+        timing.rate = Some(parse_rate(rate)?);
+    }
+
+    if timing.min_timeout > timing.max_timeout {
+        return Err(config_error("min_timeout is larger than max_timeout"));
+    }
+
     let mut rule = ShowRule::default();
 
     if let Some(show_table) = profile.get("show") {
-        let show_open = if let Some(open) = show_table.get("open") {
-            open.as_bool().expect("show rules error: open!!")
-        } else {
-            false
-        };
+        rule.open = parse_show_flag(show_table, "open")?;
+        rule.closed = parse_show_flag(show_table, "closed")?;
+        rule.filtered = parse_show_flag(show_table, "filtered")?;
+        rule.unfiltered = parse_show_flag(show_table, "unfiltered")?;
+        rule.open_filtered = parse_show_flag(show_table, "open_filtered")?;
+    }
+
+    let gateway_mac: Option<MacAddr> = gateway_mac
+        .map(|mac| {
+            mac.parse()
+                .map_err(|_| config_error(format!("invalid gateway mac: {}", mac)))
+        })
+        .transpose()?;
+
+    Ok((interface, gateway_mac, scan_type, discovery, timing, rule))
+}
 
-        let show_closed = if let Some(closed) = show_table.get("closed") {
-            closed.as_bool().expect("show rules error: closed!!")
-        } else {
-            false
+/// `{ echo = true, syn = [80, 443], ack = 80, udp = 40125 }`, echo stays
+/// on unless turned off.
+fn parse_discovery(value: &Value) -> Result<Discovery> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| config_error(format!("invalid discovery: {}", value)))?;
+
+    let mut discovery = Discovery::default();
+
+    if let Some(echo) = table.get("echo") {
+        discovery.echo = echo
+            .as_bool()
+            .ok_or_else(|| config_error(format!("invalid discovery echo: {}", echo)))?;
+    }
+
+    let ports = |key| {
+        table
+            .get(key)
+            .map(parse_port_list)
+            .unwrap_or(Ok(Vec::new()))
+    };
+    discovery.syn_ports = ports("syn")?;
+    discovery.ack_ports = ports("ack")?;
+    discovery.udp_ports = ports("udp")?;
+
+    Ok(discovery)
+}
+
+fn parse_port_list(value: &Value) -> Result<Vec<u16>> {
+    match value {
+        Value::Array(ports) => ports.iter().map(parse_port).collect(),
+        _ => Ok(vec![parse_port(value)?]),
+    }
+}
+
+/// `interface.name = "eth0"` or `interface.ip` holding any of its
+/// addresses. Without either the default route picks the interface.
+fn parse_interface(profile: &Value) -> Result<Selector> {
+    let Some(interface) = profile.get("interface") else {
+        return Ok(Selector::Auto);
+    };
+
+    match (interface.get("name"), interface.get("ip")) {
+        (Some(_), Some(_)) => Err(config_error(
+            "interface can be selected by name or by ip, not both",
+        )),
+        (Some(name), None) => Ok(Selector::Name(as_str(name)?.into())),
+        (None, Some(ip)) => {
+            Ok(Selector::Address(as_str(ip)?.parse().map_err(|_| {
+                config_error(format!("invalid interface ip: {}", ip))
+            })?))
+        }
+        (None, None) => Err(config_error("interface needs a name or an ip")),
+    }
+}
+
+fn parse_show_flag(show_table: &Value, key: &str) -> Result<bool> {
+    match show_table.get(key) {
+        Some(flag) => flag
+            .as_bool()
+            .ok_or_else(|| config_error(format!("show rules error: {}", key))),
+        None => Ok(false),
+    }
+}
+
+fn parse_switch(profile: &Value, key: &str) -> Result<bool> {
+    match profile.get(key) {
+        Some(switch) => switch
+            .as_bool()
+            .ok_or_else(|| config_error(format!("{} must be true or false", key))),
+        None => Ok(false),
+    }
+}
+
+/// One spec per `[[target]]`, so its hosts are only scanned on its own
+/// ports. What the target's or the profile's `exclude` names is left out,
+/// the merged exclusions are returned alongside.
+fn parse_targets(table: &Table, services: &Services) -> Result<(Vec<TargetSpec>, Vec<Exclude>)> {
+    let targets = table
+        .get("target")
+        .and_then(Value::as_array)
+        .ok_or_else(|| config_error("missing [[target]] entries"))?;
+
+    let profile_exclude = match table.get("profile").and_then(|x| x.get("exclude")) {
+        Some(exclude) => parse_exclude(exclude, services)?,
+        None => Exclude::default(),
+    };
+
+    let mut specs = Vec::with_capacity(targets.len());
+    let mut excludes = Vec::with_capacity(targets.len());
+
+    for item in targets {
+        let ip = item
+            .get("ip")
+            .ok_or_else(|| config_error("target without ip"))?;
+        let ports = item
+            .get("ports")
+            .ok_or_else(|| config_error("target without ports"))?;
+
+        let protocol = match item.get("protocol") {
+            Some(protocol) => match as_str(protocol)? {
+                "tcp" => Protocol::Tcp,
+                "udp" => Protocol::Udp,
+                other => return Err(config_error(format!("unsupported protocol: {}", other))),
+            },
+            None => Protocol::Tcp,
         };
 
-        let show_filtered = if let Some(filtered) = show_table.get("filtered") {
-            filtered.as_bool().expect("show rules error: filtered!!")
-        } else {
-            false
+        let mut exclude = match item.get("exclude") {
+            Some(exclude) => parse_exclude(exclude, services)?,
+            None => Exclude::default(),
         };
+        exclude.extend(&profile_exclude);
+
+        let ips: Vec<IpAddr> = parse_ip_value(ip)?
+            .into_iter()
+            .filter(|x| !exclude.ips.contains(x))
+            .collect::<BTreeSet<IpAddr>>()
+            .into_iter()
+            .collect();
+
+        let (tcp, udp) = parse_ports_value(ports, protocol, services)?.split(protocol);
+
+        specs.push(TargetSpec {
+            ips,
+            ports: exclude.filter(Protocol::Tcp, tcp),
+            udp_ports: exclude.filter(Protocol::Udp, udp),
+        });
+        excludes.push(exclude);
+    }
+
+    Ok((specs, excludes))
+}
+
+/// Hosts and ports that must never be probed.
+#[derive(Default)]
+struct Exclude {
+    ips: HashSet<IpAddr>,
+    tcp: HashSet<u16>,
+    udp: HashSet<u16>,
+}
+impl Exclude {
+    fn extend(&mut self, other: &Exclude) {
+        self.ips.extend(&other.ips);
+        self.tcp.extend(&other.tcp);
+        self.udp.extend(&other.udp);
+    }
 
-        rule.open = show_open;
-        rule.filtered = show_filtered;
-        rule.closed = show_closed;
+    /// `ports` without the excluded ones, sorted and deduplicated.
+    fn filter(&self, protocol: Protocol, ports: Vec<u16>) -> Vec<u16> {
+        let excluded = match protocol {
+            Protocol::Tcp => &self.tcp,
+            Protocol::Udp => &self.udp,
+        };
+        dedup(
+            ports
+                .into_iter()
+                .filter(|x| !excluded.contains(x))
+                .collect(),
+        )
     }
+}
+
+/// `{ ip = ..., ports = ... }`, each in any form a target accepts. Ports
+/// without a protocol prefix are excluded for both protocols, known and top
+/// ones are taken from TCP.
+fn parse_exclude(value: &Value, services: &Services) -> Result<Exclude> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| config_error(format!("invalid exclude: {}", value)))?;
 
-    let interface_ip: Ipv4Addr = interface_ip.parse().unwrap();
-    let gateway_mac: MacAddr = gateway_mac.parse().unwrap();
+    let mut exclude = Exclude::default();
 
-    (interface_ip, gateway_mac, rule)
+    if let Some(ip) = table.get("ip") {
+        exclude.ips.extend(parse_ip_value(ip)?);
+    }
+    if let Some(ports) = table.get("ports") {
+        let ports = parse_ports_value(ports, Protocol::Tcp, services)?;
+        exclude.tcp.extend(ports.tcp.iter().chain(&ports.ports));
+        exclude.udp.extend(ports.udp.iter().chain(&ports.ports));
+    }
+
+    Ok(exclude)
 }
 
-fn parse_targets(table: &Table) -> (Vec<Ipv4Addr>, Vec<u16>) {
-    let targets = table.get("target").unwrap().as_array().unwrap();
+/// An address, prefix or hostname, an array of them or a `{from, to}`
+/// range.
+fn parse_ip_value(ip: &Value) -> Result<Vec<IpAddr>> {
+    let mut ip_vec: Vec<IpAddr> = Vec::new();
 
-    let mut ip_vec: Vec<Ipv4Addr> = Vec::new();
-    let mut ports_vec: Vec<u16> = Vec::new();
+    match ip {
+        Value::String(ip) => parse_hosts(ip, &mut ip_vec)?,
 
-    for item in targets {
-        let ip = item.get("ip").unwrap();
-        let ports = item.get("ports").unwrap();
-
-        match ip {
-            Value::String(ip) => ip_vec.push(ip.parse().unwrap()),
-
-            Value::Array(ips) => ips
-                .iter()
-                .for_each(|x| ip_vec.push(x.as_str().unwrap().parse().unwrap())),
-
-            Value::Table(ips) => {
-                let from: Ipv4Addr = ips.get("from").unwrap().as_str().unwrap().parse().unwrap();
-                let to: Ipv4Addr = ips.get("to").unwrap().as_str().unwrap().parse().unwrap();
-                Ipv4AddrRange::new(from, to)
-                    .filter(|x| !x.is_broadcast() && !x.is_multicast())
-                    .for_each(|x| ip_vec.push(x));
+        Value::Array(ips) => {
+            for x in ips {
+                parse_hosts(as_str(x)?, &mut ip_vec)?;
             }
+        }
 
-            _ => panic!("Unsupported Ip type"),
+        Value::Table(ips) => {
+            let from = parse_ip(as_str(get_key(ips, "from")?)?)?;
+            let to = parse_ip(as_str(get_key(ips, "to")?)?)?;
+            parse_range(from, to, &mut ip_vec)?;
         }
 
-        match ports {
-            Value::Integer(port) => ports_vec.push(*port as u16),
+        _ => return Err(config_error(format!("unsupported ip type: {}", ip))),
+    }
 
-            Value::Array(ports) => ports
-                .iter()
-                .for_each(|x| ports_vec.push(x.as_integer().unwrap() as u16)),
+    Ok(ip_vec)
+}
 
-            Value::Table(ports) => {
-                let from = ports.get("from").unwrap().as_integer().unwrap() as u16;
-                let to = ports.get("to").unwrap().as_integer().unwrap() as u16;
-                (from..=to).for_each(|x| ports_vec.push(x));
-            }
+/// A port, an array of them, a `{from, to}` range, the `{top = N}` most
+/// frequently open ports, `"all"`, `"known"` or an nmap-style string such
+/// as `"22,80,8000-,U:53"`. `protocol` picks which of the `services` ports
+/// are known or top.
+fn parse_ports_value(ports: &Value, protocol: Protocol, services: &Services) -> Result<PortSpec> {
+    let mut ports_vec: Vec<u16> = Vec::new();
+
+    match ports {
+        Value::Integer(_) => ports_vec.push(parse_port(ports)?),
 
-            Value::String(s) => {
-                if s == "all" {
-                    (0..=65535).for_each(|x| ports_vec.push(x));
-                } else if s == "known" {
-                    let ports_known = config::get_ports_known();
-                    ports_vec.extend_from_slice(ports_known);
-                } else {
-                    panic!("Unsupported ports type");
-                }
+        Value::Array(ports) => {
+            for x in ports {
+                ports_vec.push(parse_port(x)?);
             }
+        }
 
-            _ => panic!("Unsupported ports type"),
+        Value::Table(ports) => match ports.get("top") {
+            Some(top) => {
+                let n = top
+                    .as_integer()
+                    .and_then(|x| usize::try_from(x).ok())
+                    .filter(|x| *x > 0)
+                    .ok_or_else(|| config_error(format!("invalid top ports: {}", top)))?;
+                ports_vec
+                    .extend_from_slice(listed(services.top(&protocol.to_string(), n), protocol)?);
+            }
+            None => {
+                let from = parse_port(get_key(ports, "from")?)?;
+                let to = parse_port(get_key(ports, "to")?)?;
+                (from..=to).for_each(|x| ports_vec.push(x));
+            }
+        },
+
+        Value::String(s) => {
+            if s == "all" {
+                (0..=65535).for_each(|x| ports_vec.push(x));
+            } else if s == "known" {
+                ports_vec
+                    .extend_from_slice(listed(services.known(&protocol.to_string()), protocol)?);
+            } else {
+                return s.parse().map_err(config_error);
+            }
         }
+
+        _ => return Err(config_error(format!("unsupported ports type: {}", ports))),
     }
 
-    let ip_vec = ip_vec
-        .into_iter()
-        .collect::<BTreeSet<Ipv4Addr>>()
-        .into_iter()
-        .collect();
+    Ok(PortSpec {
+        ports: ports_vec,
+        ..PortSpec::default()
+    })
+}
 
-    let ports_vec = ports_vec
+/// Known or top ports, which the service database may not list any of for
+/// `protocol`.
+fn listed(ports: &[u16], protocol: Protocol) -> Result<&[u16]> {
+    if ports.is_empty() {
+        return Err(config_error(format!(
+            "no {} services listed, set services in the profile",
+            protocol
+        )));
+    }
+    Ok(ports)
+}
+
+fn dedup(ports: Vec<u16>) -> Vec<u16> {
+    ports
         .into_iter()
         .collect::<BTreeSet<u16>>()
         .into_iter()
+        .collect()
+}
+
+fn get_key<'a>(table: &'a Table, key: &str) -> Result<&'a Value> {
+    table
+        .get(key)
+        .ok_or_else(|| config_error(format!("missing key: {}", key)))
+}
+
+fn as_str(value: &Value) -> Result<&str> {
+    value
+        .as_str()
+        .ok_or_else(|| config_error(format!("expected a string, found {}", value)))
+}
+
+fn parse_ip(ip: &str) -> Result<IpAddr> {
+    ip.parse()
+        .map_err(|_| config_error(format!("invalid ip address: {}", ip)))
+}
+
+/// A single address, every address of a prefix such as `"10.0.0.0/24"`
+/// or `"2001:db8::/120"`, or every address a hostname resolves to.
+fn parse_hosts(hosts: &str, ip_vec: &mut Vec<IpAddr>) -> Result<()> {
+    if let Ok(ip) = hosts.parse() {
+        ip_vec.push(ip);
+        return Ok(());
+    }
+
+    if hosts.contains('/') {
+        let net: IpNet = hosts
+            .parse()
+            .map_err(|_| config_error(format!("invalid prefix: {}", hosts)))?;
+        let net = net.trunc();
+
+        return parse_range(net.network(), net.broadcast(), ip_vec);
+    }
+
+    let resolved: Vec<IpAddr> = (hosts, 0)
+        .to_socket_addrs()
+        .map_err(|e| config_error(format!("can not resolve {}: {}", hosts, e)))?
+        .map(|x| x.ip())
         .collect();
+    if resolved.is_empty() {
+        return Err(config_error(format!("{} has no address", hosts)));
+    }
+
+    ip_vec.extend(resolved);
+    Ok(())
+}
+
+/// Every address from `from` to `to`, skipping broadcast and multicast
+/// ones. Both ends have to be of the same family.
+fn parse_range(from: IpAddr, to: IpAddr, ip_vec: &mut Vec<IpAddr>) -> Result<()> {
+    let hosts = match (from, to) {
+        (IpAddr::V4(from), IpAddr::V4(to)) => u128::from(u32::from(to).saturating_sub(from.into())),
+        (IpAddr::V6(from), IpAddr::V6(to)) => u128::from(to).saturating_sub(from.into()),
+        _ => 0,
+    };
+    if hosts >= MAX_RANGE_HOSTS {
+        return Err(config_error(format!(
+            "{} - {} holds more than {} addresses",
+            from, to, MAX_RANGE_HOSTS
+        )));
+    }
+
+    match (from, to) {
+        (IpAddr::V4(from), IpAddr::V4(to)) => Ipv4AddrRange::new(from, to)
+            .filter(|x| !x.is_broadcast() && !x.is_multicast())
+            .for_each(|x| ip_vec.push(IpAddr::V4(x))),
+
+        (IpAddr::V6(from), IpAddr::V6(to)) => Ipv6AddrRange::new(from, to)
+            .filter(|x| !x.is_multicast() && !x.is_unspecified())
+            .for_each(|x| ip_vec.push(IpAddr::V6(x))),
+
+        _ => {
+            return Err(config_error(format!(
+                "range mixes address families: {} - {}",
+                from, to
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_port(value: &Value) -> Result<u16> {
+    value
+        .as_integer()
+        .and_then(|x| u16::try_from(x).ok())
+        .ok_or_else(|| config_error(format!("invalid port: {}", value)))
+}
+
+fn parse_template(value: &Value) -> Result<TimingTemplate> {
+    match value {
+        Value::String(s) => s.parse().map_err(config_error),
+        Value::Integer(x) => x.to_string().parse().map_err(config_error),
+        _ => Err(config_error(format!("invalid timing template: {}", value))),
+    }
+}
+
+fn parse_rate(value: &Value) -> Result<f64> {
+    let rate = match value {
+        Value::Integer(x) => *x as f64,
+        Value::Float(x) => *x,
+        _ => return Err(config_error(format!("invalid rate: {}", value))),
+    };
+
+    if rate > 0.0 {
+        Ok(rate)
+    } else {
+        Err(config_error(format!("rate must be positive: {}", value)))
+    }
+}
+
+fn parse_millis(value: &Value) -> Result<Duration> {
+    value
+        .as_integer()
+        .and_then(|x| u64::try_from(x).ok())
+        .map(Duration::from_millis)
+        .ok_or_else(|| config_error(format!("invalid duration in milliseconds: {}", value)))
+}
 
-    (ip_vec, ports_vec)
+fn config_error(msg: impl Into<String>) -> ScanError {
+    ScanError::Config(msg.into())
 }
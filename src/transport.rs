@@ -0,0 +1,129 @@
+pub mod memory;
+
+use std::{
+    io,
+    net::Ipv4Addr,
+    time::{Duration, Instant},
+};
+
+use pnet::{
+    datalink::{self, DataLinkReceiver, DataLinkSender, NetworkInterface},
+    util::MacAddr,
+};
+
+use crate::{
+    error::{Result, ScanError},
+    route,
+};
+
+pub use memory::MemoryTransport;
+
+/// How long a datalink read blocks before `Channel::recv` re-checks its deadline.
+const READ_POLL: Duration = Duration::from_millis(10);
+
+/// Somewhere ethernet frames can be sent to and read back from.
+///
+/// Every call to `open` hands out an independent channel that sees all
+/// inbound traffic, just like opening the same interface twice with pnet.
+pub trait Transport: Send + Sync {
+    fn interface(&self) -> &NetworkInterface;
+
+    fn open(&self) -> Result<Box<dyn Channel>>;
+
+    /// The MAC the system already knows for `ip`, asked before any ARP
+    /// request goes out. Nothing is cached by default.
+    fn neighbor(&self, _ip: Ipv4Addr) -> Option<MacAddr> {
+        None
+    }
+}
+
+pub trait Channel: Send {
+    fn send(&mut self, frame: &[u8]) -> Result<()>;
+
+    /// Waits up to `timeout` for the next frame, `Ok(None)` when nothing arrived.
+    fn recv(&mut self, timeout: Duration) -> Result<Option<&[u8]>>;
+}
+
+/// The real network, reached through a pnet datalink channel.
+pub struct DatalinkTransport {
+    interface: NetworkInterface,
+}
+
+impl DatalinkTransport {
+    pub fn new(interface: NetworkInterface) -> Self {
+        DatalinkTransport { interface }
+    }
+}
+
+impl Transport for DatalinkTransport {
+    fn interface(&self) -> &NetworkInterface {
+        &self.interface
+    }
+
+    fn open(&self) -> Result<Box<dyn Channel>> {
+        let config = datalink::Config {
+            read_timeout: Some(READ_POLL),
+            ..Default::default()
+        };
+
+        match datalink::channel(&self.interface, config) {
+            Ok(datalink::Channel::Ethernet(tx, rx)) => Ok(Box::new(DatalinkChannel {
+                tx,
+                rx,
+                buf: Vec::new(),
+            })),
+            Ok(_) => Err(ScanError::Interface(format!(
+                "{} does not provide an ethernet channel",
+                self.interface.name
+            ))),
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                Err(ScanError::Permission(format!(
+                    "can not open a raw channel on {}: {}",
+                    self.interface.name, e
+                )))
+            }
+            Err(e) => Err(ScanError::Io(e)),
+        }
+    }
+
+    fn neighbor(&self, ip: Ipv4Addr) -> Option<MacAddr> {
+        route::neighbor(ip)
+    }
+}
+
+struct DatalinkChannel {
+    tx: Box<dyn DataLinkSender>,
+    rx: Box<dyn DataLinkReceiver>,
+    buf: Vec<u8>,
+}
+
+impl Channel for DatalinkChannel {
+    fn send(&mut self, frame: &[u8]) -> Result<()> {
+        match self.tx.send_to(frame, None) {
+            Some(res) => Ok(res?),
+            None => Err(ScanError::Io(io::Error::other(
+                "datalink sender has no room for the packet",
+            ))),
+        }
+    }
+
+    fn recv(&mut self, timeout: Duration) -> Result<Option<&[u8]>> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match self.rx.next() {
+                Ok(frame) => {
+                    self.buf.clear();
+                    self.buf.extend_from_slice(frame);
+                    return Ok(Some(&self.buf));
+                }
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+                    if Instant::now() >= deadline {
+                        return Ok(None);
+                    }
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use pnet::datalink::NetworkInterface;
+
+use super::{Channel, Transport};
+use crate::error::Result;
+
+type Responder = dyn Fn(&[u8]) -> Vec<Vec<u8>> + Send + Sync;
+
+/// An in-memory network for tests.
+///
+/// Every frame sent on any channel is handed to the responder, and whatever
+/// frames it returns are delivered to all open channels.
+pub struct MemoryTransport {
+    interface: NetworkInterface,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    responder: Box<Responder>,
+    taps: Mutex<Vec<Sender<Vec<u8>>>>,
+    sent: Mutex<Vec<Vec<u8>>>,
+}
+
+impl Shared {
+    fn deliver(&self, frame: Vec<u8>) {
+        self.taps
+            .lock()
+            .unwrap()
+            .retain(|tap| tap.send(frame.clone()).is_ok());
+    }
+}
+
+impl MemoryTransport {
+    pub fn new(
+        interface: NetworkInterface,
+        responder: impl Fn(&[u8]) -> Vec<Vec<u8>> + Send + Sync + 'static,
+    ) -> Self {
+        MemoryTransport {
+            interface,
+            shared: Arc::new(Shared {
+                responder: Box::new(responder),
+                taps: Mutex::new(Vec::new()),
+                sent: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Pushes an unsolicited frame to every open channel.
+    pub fn inject(&self, frame: Vec<u8>) {
+        self.shared.deliver(frame);
+    }
+
+    /// Every frame sent so far, in order.
+    pub fn sent_frames(&self) -> Vec<Vec<u8>> {
+        self.shared.sent.lock().unwrap().clone()
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn interface(&self) -> &NetworkInterface {
+        &self.interface
+    }
+
+    fn open(&self) -> Result<Box<dyn Channel>> {
+        let (tx, rx) = mpsc::channel();
+        self.shared.taps.lock().unwrap().push(tx);
+
+        Ok(Box::new(MemoryChannel {
+            shared: self.shared.clone(),
+            rx,
+            buf: Vec::new(),
+        }))
+    }
+}
+
+struct MemoryChannel {
+    shared: Arc<Shared>,
+    rx: Receiver<Vec<u8>>,
+    buf: Vec<u8>,
+}
+
+impl Channel for MemoryChannel {
+    fn send(&mut self, frame: &[u8]) -> Result<()> {
+        self.shared.sent.lock().unwrap().push(frame.to_vec());
+
+        for reply in (self.shared.responder)(frame) {
+            self.shared.deliver(reply);
+        }
+
+        Ok(())
+    }
+
+    fn recv(&mut self, timeout: Duration) -> Result<Option<&[u8]>> {
+        match self.rx.recv_timeout(timeout) {
+            Ok(frame) => {
+                self.buf = frame;
+                Ok(Some(&self.buf))
+            }
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => Ok(None),
+        }
+    }
+}
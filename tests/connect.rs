@@ -0,0 +1,177 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, TcpListener, UdpSocket},
+    sync::{mpsc, Arc},
+    thread,
+    time::Duration,
+};
+
+use pnet::{datalink::NetworkInterface, util::MacAddr};
+use syn_port_scanner::{
+    simnet,
+    transport::{Channel, Transport},
+    Protocol, Result, ScanConfig, ScanError, ScanResult, ScanType, Scanner,
+};
+
+const GATEWAY_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 0xfe);
+
+/// A listening TCP port, a refused one, an answering UDP port and an
+/// unreachable one, all on the loopback interface.
+struct Loopback {
+    _tcp: TcpListener,
+    tcp_open: u16,
+    tcp_closed: u16,
+    udp_open: u16,
+    udp_closed: u16,
+}
+
+impl Loopback {
+    fn new() -> Self {
+        let tcp = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let tcp_closed = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let udp = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let udp_open = udp.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let mut buf = [0_u8; 512];
+            while let Ok((len, peer)) = udp.recv_from(&mut buf) {
+                let _ = udp.send_to(&buf[..len], peer);
+            }
+        });
+        let udp_closed = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        Loopback {
+            tcp_open: tcp.local_addr().unwrap().port(),
+            _tcp: tcp,
+            tcp_closed,
+            udp_open,
+            udp_closed,
+        }
+    }
+
+    fn config(&self, scan_type: ScanType) -> ScanConfig {
+        let mut config = ScanConfig::new(
+            Ipv4Addr::LOCALHOST,
+            GATEWAY_MAC,
+            vec![Ipv4Addr::LOCALHOST.into()],
+            vec![self.tcp_open, self.tcp_closed],
+        );
+        config.targets[0].udp_ports = vec![self.udp_open, self.udp_closed];
+        config.scan_type = scan_type;
+        config
+    }
+
+    fn check(&self, result: &ScanResult) {
+        let ports = |protocol| {
+            move |results: &[syn_port_scanner::PortResult]| {
+                results
+                    .iter()
+                    .filter(|x| x.protocol == protocol)
+                    .map(|x| x.socket.port())
+                    .collect::<Vec<u16>>()
+            }
+        };
+        let tcp = ports(Protocol::Tcp);
+        let udp = ports(Protocol::Udp);
+
+        assert_eq!(result.reachable, vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]);
+        assert_eq!(tcp(&result.open), vec![self.tcp_open]);
+        assert_eq!(tcp(&result.closed), vec![self.tcp_closed]);
+        assert_eq!(udp(&result.open), vec![self.udp_open]);
+        assert_eq!(udp(&result.closed), vec![self.udp_closed]);
+        assert!(result.filtered.is_empty());
+    }
+}
+
+/// A raw transport the process is not allowed to open.
+struct Denied(NetworkInterface);
+
+impl Transport for Denied {
+    fn interface(&self) -> &NetworkInterface {
+        &self.0
+    }
+
+    fn open(&self) -> Result<Box<dyn Channel>> {
+        Err(ScanError::Permission("raw sockets need CAP_NET_RAW".into()))
+    }
+}
+
+fn denied() -> Arc<Denied> {
+    Arc::new(Denied(simnet::interface(
+        MacAddr(0x02, 0, 0, 0, 0, 1),
+        Ipv4Addr::LOCALHOST,
+        8,
+    )))
+}
+
+#[test]
+fn connect_scan_classifies_loopback_ports() {
+    let loopback = Loopback::new();
+
+    let result = Scanner::new(loopback.config(ScanType::Connect))
+        .run()
+        .unwrap();
+
+    loopback.check(&result);
+}
+
+#[test]
+fn failed_connections_only_filter_their_own_port() {
+    let loopback = Loopback::new();
+    let mut config = loopback.config(ScanType::Connect);
+    // a link-local address without a scope is refused by connect() itself
+    let unscoped = IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+    config.targets[0].ips.push(unscoped);
+    config.timing.retries = u8::MAX;
+
+    let mut result = Scanner::new(config).run().unwrap();
+
+    assert!(result.filtered.iter().all(|x| x.socket.ip() == unscoped));
+    assert_eq!(result.filtered.len(), 4);
+    result.filtered.clear();
+    loopback.check(&result);
+}
+
+#[test]
+fn a_scan_whose_sockets_all_fail_still_ends() {
+    let unscoped = IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+    let mut config = ScanConfig::new(Ipv4Addr::LOCALHOST, GATEWAY_MAC, vec![unscoped], vec![80]);
+    config.targets[0].udp_ports = vec![53];
+    config.scan_type = ScanType::Connect;
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = tx.send(Scanner::new(config).run().unwrap());
+    });
+    let result = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+
+    assert_eq!(result.filtered.len(), 2);
+    assert!(result.open.is_empty() && result.closed.is_empty());
+}
+
+#[test]
+fn syn_scan_falls_back_to_connect_without_raw_access() {
+    let loopback = Loopback::new();
+
+    let result = Scanner::with_transport(loopback.config(ScanType::Syn), denied())
+        .run()
+        .unwrap();
+
+    loopback.check(&result);
+}
+
+#[test]
+fn other_raw_scans_report_the_missing_permission() {
+    let loopback = Loopback::new();
+
+    let result = Scanner::with_transport(loopback.config(ScanType::Fin), denied()).run();
+
+    assert!(matches!(result, Err(ScanError::Permission(_))));
+}
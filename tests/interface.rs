@@ -0,0 +1,98 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use pnet::{ipnetwork::IpNetwork, util::MacAddr};
+use syn_port_scanner::{
+    interface::{self, Link},
+    simnet, toml_parser, ScanError, Selector,
+};
+
+fn profile(interface: &str) -> String {
+    format!(
+        r#"
+        [profile]
+        {}
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = 22
+        "#,
+        interface
+    )
+}
+
+#[test]
+fn selectors_parse_from_names_addresses_and_auto() {
+    assert_eq!("eth0".parse(), Ok(Selector::Name("eth0".into())));
+    assert_eq!(
+        "10.0.0.2".parse(),
+        Ok(Selector::Address(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))))
+    );
+    assert_eq!(
+        "fe80::1".parse::<Selector>().unwrap(),
+        Selector::Address("fe80::1".parse().unwrap())
+    );
+    assert_eq!("auto".parse(), Ok(Selector::Auto));
+}
+
+#[test]
+fn profiles_select_the_interface_by_name_ip_or_route() {
+    let by_name = toml_parser::parse_str(&profile(r#"interface.name = "eth1""#)).unwrap();
+    assert_eq!(by_name.scan.interface, Selector::Name("eth1".into()));
+
+    let by_ip = toml_parser::parse_str(&profile(r#"interface.ip = "2001:db8::10""#)).unwrap();
+    assert_eq!(
+        by_ip.scan.interface,
+        Selector::Address("2001:db8::10".parse().unwrap())
+    );
+
+    let by_route = toml_parser::parse_str(&profile("")).unwrap();
+    assert_eq!(by_route.scan.interface, Selector::Auto);
+
+    let both = toml_parser::parse_str(&profile(
+        r#"interface = { name = "eth1", ip = "10.0.0.2" }"#,
+    ));
+    assert!(matches!(both, Err(ScanError::Config(_))));
+}
+
+#[test]
+fn unknown_interfaces_list_the_available_ones() {
+    let err = interface::find(&Selector::Name("no-such-nic0".into())).unwrap_err();
+
+    assert!(matches!(err, ScanError::Interface(_)));
+    assert!(err
+        .to_string()
+        .contains("no interface is named no-such-nic0"));
+    assert!(err.to_string().contains("available:"));
+}
+
+#[test]
+fn a_secondary_address_selected_is_the_source_of_its_family() {
+    let primary = Ipv4Addr::new(192, 168, 1, 10);
+    let secondary = Ipv4Addr::new(192, 168, 7, 10);
+    let global: Ipv6Addr = "2001:db8::10".parse().unwrap();
+    let second_global: Ipv6Addr = "2001:db8:1::10".parse().unwrap();
+
+    let mut nic = simnet::interface(MacAddr(0x02, 0, 0, 0, 0, 1), primary, 24);
+    nic.ips.extend([
+        IpNetwork::new(secondary.into(), 24).unwrap(),
+        IpNetwork::new(global.into(), 64).unwrap(),
+        IpNetwork::new(second_global.into(), 64).unwrap(),
+    ]);
+    let interfaces = [nic];
+
+    let source = |selector: Selector| {
+        let found = interface::select(&interfaces, &selector).unwrap();
+        let link = Link::new(&found, None).unwrap();
+        (link.src_ipv4, link.src_ipv6)
+    };
+
+    assert_eq!(
+        source("sim0".parse().unwrap()),
+        (Some(primary), Some(global))
+    );
+    assert_eq!(source(secondary.into()), (Some(secondary), Some(global)));
+    assert_eq!(
+        source(IpAddr::V6(second_global).into()),
+        (Some(primary), Some(second_global))
+    );
+}
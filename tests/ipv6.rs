@@ -0,0 +1,224 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+
+use pnet::{
+    ipnetwork::{IpNetwork, Ipv6Network},
+    packet::{
+        ethernet::{EtherTypes, EthernetPacket},
+        icmpv6::Icmpv6Types,
+        ip::IpNextHeaderProtocols,
+        ipv6::{Ipv6Packet, MutableIpv6Packet},
+        tcp::{self, TcpFlags, TcpPacket},
+        Packet,
+    },
+    util::MacAddr,
+};
+use syn_port_scanner::{
+    simnet::{self, EchoBehavior, PortBehavior, VirtualHost, VirtualNetwork},
+    toml_parser,
+    transport::MemoryTransport,
+    PortResult, Protocol, ScanConfig, Scanner,
+};
+
+const LOCAL_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 1);
+const GATEWAY_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 0xfe);
+const LOCAL_IP: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 10);
+const LOCAL_IPV6: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 0x10);
+
+const WEB: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 1));
+const WEB6: IpAddr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 2, 0, 0, 0, 1));
+const DARK6: IpAddr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 2, 0, 0, 0, 2));
+const NEIGHBOR6: IpAddr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 0x20));
+
+fn interface() -> pnet::datalink::NetworkInterface {
+    let mut interface = simnet::interface(LOCAL_MAC, LOCAL_IP, 24);
+    interface
+        .ips
+        .push(IpNetwork::V6(Ipv6Network::new(LOCAL_IPV6, 64).unwrap()));
+    interface
+}
+
+fn sockets(results: &[PortResult], protocol: Protocol) -> Vec<SocketAddr> {
+    let mut sockets: Vec<SocketAddr> = results
+        .iter()
+        .filter(|x| x.protocol == protocol)
+        .map(|x| x.socket)
+        .collect();
+    sockets.sort();
+    sockets
+}
+
+#[test]
+fn dual_stack_hosts_are_scanned_over_both_families() {
+    let network = VirtualNetwork::new(interface(), GATEWAY_MAC)
+        .host(VirtualHost::new(WEB).port(80, PortBehavior::Open))
+        .host(
+            VirtualHost::new(WEB6)
+                .port(22, PortBehavior::Open)
+                .port(25, PortBehavior::Unreachable(1))
+                .udp_port(53, PortBehavior::Open)
+                .udp_port(161, PortBehavior::Unreachable(1)),
+        );
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB, WEB6], vec![22, 25, 80]);
+    config.targets[0].udp_ports = vec![53, 161, 500];
+    config.timing.retries = 0;
+
+    let result = Scanner::with_transport(config, Arc::new(network))
+        .run()
+        .unwrap();
+
+    assert!(result.reachable.contains(&WEB6));
+    assert_eq!(
+        sockets(&result.open, Protocol::Tcp),
+        vec![SocketAddr::new(WEB, 80), SocketAddr::new(WEB6, 22)]
+    );
+    assert_eq!(
+        sockets(&result.filtered, Protocol::Tcp),
+        vec![SocketAddr::new(WEB6, 25)]
+    );
+    assert_eq!(
+        sockets(&result.open, Protocol::Udp),
+        vec![SocketAddr::new(WEB6, 53)]
+    );
+    assert_eq!(
+        sockets(&result.filtered, Protocol::Udp),
+        vec![SocketAddr::new(WEB6, 161)]
+    );
+    assert!(sockets(&result.closed, Protocol::Udp).contains(&SocketAddr::new(WEB6, 500)));
+}
+
+#[test]
+fn ipv6_hosts_are_discovered_by_echo_or_neighbor_solicitation() {
+    // neither pings back, but the neighbor shares our prefix and answers
+    // the solicitation
+    let network = VirtualNetwork::new(interface(), GATEWAY_MAC)
+        .host(VirtualHost::new(WEB6).port(22, PortBehavior::Open))
+        .host(
+            VirtualHost::new(DARK6)
+                .echo(EchoBehavior::Silent)
+                .port(22, PortBehavior::Open),
+        )
+        .host(
+            VirtualHost::new(NEIGHBOR6)
+                .echo(EchoBehavior::Silent)
+                .port(22, PortBehavior::Open),
+        );
+
+    let mut config = ScanConfig::new(
+        LOCAL_IP,
+        GATEWAY_MAC,
+        vec![WEB6, DARK6, NEIGHBOR6],
+        vec![22],
+    );
+    config.timing.retries = 0;
+
+    let mut result = Scanner::with_transport(config, Arc::new(network))
+        .run()
+        .unwrap();
+
+    result.reachable.sort();
+    assert_eq!(result.reachable, vec![NEIGHBOR6, WEB6]);
+    assert_eq!(
+        sockets(&result.open, Protocol::Tcp),
+        vec![SocketAddr::new(NEIGHBOR6, 22), SocketAddr::new(WEB6, 22)]
+    );
+}
+
+#[test]
+fn ipv6_syn_carries_a_valid_pseudo_header_checksum() {
+    // answers the ping by echoing the request back as a reply
+    let transport = Arc::new(MemoryTransport::new(interface(), |frame| {
+        let mut reply = frame.to_vec();
+        let mut ip = MutableIpv6Packet::new(&mut reply[14..]).unwrap();
+        if ip.get_next_header() != IpNextHeaderProtocols::Icmpv6 {
+            return vec![];
+        }
+        let (src, dest) = (ip.get_source(), ip.get_destination());
+        ip.set_source(dest);
+        ip.set_destination(src);
+        reply[14 + 40] = Icmpv6Types::EchoReply.0;
+        vec![reply]
+    }));
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB6], vec![443]);
+    config.timing.retries = 0;
+    Scanner::with_transport(config, transport.clone())
+        .run()
+        .unwrap();
+
+    let frames = transport.sent_frames();
+    let eth = EthernetPacket::new(&frames[1]).unwrap();
+    assert_eq!(eth.get_ethertype(), EtherTypes::Ipv6);
+    assert_eq!(eth.get_destination(), GATEWAY_MAC);
+
+    let ip = Ipv6Packet::new(eth.payload()).unwrap();
+    assert_eq!(ip.get_next_header(), IpNextHeaderProtocols::Tcp);
+    assert_eq!(ip.get_source(), LOCAL_IPV6);
+    assert_eq!(IpAddr::V6(ip.get_destination()), WEB6);
+    assert_eq!(ip.get_payload_length() as usize, ip.payload().len());
+
+    let syn = TcpPacket::new(ip.payload()).unwrap();
+    assert_eq!(syn.get_flags(), TcpFlags::SYN);
+    assert_eq!(syn.get_destination(), 443);
+    assert_eq!(
+        syn.get_checksum(),
+        tcp::ipv6_checksum(&syn, &ip.get_source(), &ip.get_destination())
+    );
+}
+
+#[test]
+fn profiles_accept_ipv6_addresses_ranges_and_prefixes() {
+    let profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        gateway.mac = "02:00:00:00:00:fe"
+
+        [[target]]
+        ip = ["10.0.0.1", "2001:db8::1"]
+        ports = 22
+
+        [[target]]
+        ip = { from = "2001:db8::fe", to = "2001:db8::101" }
+        ports = 22
+
+        [[target]]
+        ip = "2001:db8:1::/126"
+        ports = 22
+        "#,
+    )
+    .unwrap();
+
+    let ip = |x: &str| x.parse::<IpAddr>().unwrap();
+    assert_eq!(
+        profile.scan.hosts(),
+        vec![
+            ip("10.0.0.1"),
+            ip("2001:db8::1"),
+            ip("2001:db8::fe"),
+            ip("2001:db8::ff"),
+            ip("2001:db8::100"),
+            ip("2001:db8::101"),
+            ip("2001:db8:1::"),
+            ip("2001:db8:1::1"),
+            ip("2001:db8:1::2"),
+            ip("2001:db8:1::3"),
+        ]
+    );
+
+    let too_large = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        gateway.mac = "02:00:00:00:00:fe"
+
+        [[target]]
+        ip = "2001:db8::/64"
+        ports = 22
+        "#,
+    );
+    assert!(too_large.is_err());
+}
@@ -0,0 +1,134 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+use pnet::{
+    datalink::NetworkInterface,
+    ipnetwork::{IpNetwork, Ipv4Network},
+    packet::{
+        ethernet::{EtherTypes, EthernetPacket, MutableEthernetPacket},
+        icmp::{IcmpTypes, MutableIcmpPacket},
+        ip::IpNextHeaderProtocols,
+        ipv4::{self, Ipv4Packet, MutableIpv4Packet},
+        tcp::{self, MutableTcpPacket, TcpFlags, TcpPacket},
+        Packet,
+    },
+    util::MacAddr,
+};
+use syn_port_scanner::{transport::MemoryTransport, PortResult, ScanConfig, Scanner};
+
+const LOCAL_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 1);
+const GATEWAY_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 0xfe);
+const LOCAL_IP: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 1);
+const HOST: Ipv4Addr = Ipv4Addr::new(10, 0, 1, 2);
+const SILENT_HOST: Ipv4Addr = Ipv4Addr::new(10, 0, 1, 3);
+
+fn interface() -> NetworkInterface {
+    NetworkInterface {
+        name: "mem0".into(),
+        description: String::new(),
+        index: 1,
+        mac: Some(LOCAL_MAC),
+        ips: vec![IpNetwork::V4(Ipv4Network::new(LOCAL_IP, 24).unwrap())],
+        flags: 0,
+    }
+}
+
+fn reply(request: &Ipv4Packet, protocol_len: usize, fill: impl FnOnce(&mut [u8])) -> Vec<u8> {
+    let mut buf = vec![0_u8; 14 + 20 + protocol_len];
+    fill(&mut buf[34..]);
+
+    let mut ip = MutableIpv4Packet::new(&mut buf[14..34]).unwrap();
+    ip.set_version(4);
+    ip.set_header_length(5);
+    ip.set_total_length((20 + protocol_len) as u16);
+    ip.set_ttl(64);
+    ip.set_next_level_protocol(request.get_next_level_protocol());
+    ip.set_source(request.get_destination());
+    ip.set_destination(request.get_source());
+    ip.set_checksum(ipv4::checksum(&ip.to_immutable()));
+
+    let mut eth = MutableEthernetPacket::new(&mut buf[..14]).unwrap();
+    eth.set_source(GATEWAY_MAC);
+    eth.set_destination(LOCAL_MAC);
+    eth.set_ethertype(EtherTypes::Ipv4);
+
+    buf
+}
+
+/// `HOST` answers pings, has port 22 open and port 23 closed and drops
+/// everything else; `SILENT_HOST` never answers.
+fn respond(frame: &[u8]) -> Vec<Vec<u8>> {
+    let eth = EthernetPacket::new(frame).unwrap();
+    let ip = Ipv4Packet::new(eth.payload()).unwrap();
+
+    if ip.get_destination() != HOST {
+        return vec![];
+    }
+
+    match ip.get_next_level_protocol() {
+        IpNextHeaderProtocols::Icmp => vec![reply(&ip, 8, |buf| {
+            buf.copy_from_slice(&ip.payload()[..8]);
+            let mut icmp = MutableIcmpPacket::new(buf).unwrap();
+            icmp.set_icmp_type(IcmpTypes::EchoReply);
+        })],
+
+        IpNextHeaderProtocols::Tcp => {
+            let syn = TcpPacket::new(ip.payload()).unwrap();
+            let flags = match syn.get_destination() {
+                _ if syn.get_flags() != TcpFlags::SYN => return vec![],
+                22 => TcpFlags::SYN | TcpFlags::ACK,
+                23 => TcpFlags::RST | TcpFlags::ACK,
+                _ => return vec![],
+            };
+
+            vec![reply(&ip, 20, |buf| {
+                let mut tcp = MutableTcpPacket::new(buf).unwrap();
+                tcp.set_source(syn.get_destination());
+                tcp.set_destination(syn.get_source());
+                tcp.set_data_offset(5);
+                tcp.set_flags(flags);
+                tcp.set_checksum(tcp::ipv4_checksum(&tcp.to_immutable(), &HOST, &LOCAL_IP));
+            })]
+        }
+
+        _ => vec![],
+    }
+}
+
+#[test]
+fn syn_scan_classifies_ports_through_memory_transport() {
+    let transport = Arc::new(MemoryTransport::new(interface(), respond));
+
+    let mut config = ScanConfig::new(
+        LOCAL_IP,
+        GATEWAY_MAC,
+        vec![HOST.into(), SILENT_HOST.into()],
+        vec![22, 23, 80],
+    );
+    config.timing.retries = 0;
+
+    let result = Scanner::with_transport(config, transport.clone())
+        .run()
+        .unwrap();
+
+    assert_eq!(result.reachable, vec![IpAddr::V4(HOST)]);
+    let sockets =
+        |results: &[PortResult]| -> Vec<SocketAddr> { results.iter().map(|x| x.socket).collect() };
+    assert_eq!(
+        sockets(&result.open),
+        vec![SocketAddr::new(HOST.into(), 22)]
+    );
+    assert_eq!(
+        sockets(&result.closed),
+        vec![SocketAddr::new(HOST.into(), 23)]
+    );
+    assert_eq!(
+        sockets(&result.filtered),
+        vec![SocketAddr::new(HOST.into(), 80)]
+    );
+
+    // two echo requests, three SYNs and the RST tearing down port 22
+    assert_eq!(transport.sent_frames().len(), 6);
+}
@@ -0,0 +1,213 @@
+use std::net::IpAddr;
+
+use syn_port_scanner::{toml_parser, PortSpec, ScanError};
+
+fn ip(ip: &str) -> IpAddr {
+    ip.parse().unwrap()
+}
+
+#[test]
+fn targets_accept_ipv4_prefixes_and_hostnames() {
+    let profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+
+        [[target]]
+        ip = ["10.0.0.0/30", "10.0.1.7/31"]
+        ports = 22
+
+        [[target]]
+        ip = "localhost"
+        ports = 22
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        profile.scan.targets[0].ips,
+        vec![
+            ip("10.0.0.0"),
+            ip("10.0.0.1"),
+            ip("10.0.0.2"),
+            ip("10.0.0.3"),
+            ip("10.0.1.6"),
+            ip("10.0.1.7"),
+        ]
+    );
+    assert!(profile.scan.targets[1].ips.iter().all(|x| x.is_loopback()));
+    assert!(!profile.scan.targets[1].ips.is_empty());
+
+    let unresolvable = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+
+        [[target]]
+        ip = "no-such-host.invalid"
+        ports = 22
+        "#,
+    );
+    assert!(matches!(unresolvable, Err(ScanError::Config(_))));
+}
+
+#[test]
+fn oversized_ipv4_prefixes_and_ranges_are_refused() {
+    for ip in [
+        r#""0.0.0.0/0""#,
+        r#""10.0.0.0/15""#,
+        r#"{ from = "10.0.0.0", to = "10.2.0.0" }"#,
+    ] {
+        let profile = toml_parser::parse_str(&format!(
+            r#"
+            [profile]
+            interface.ip = "192.168.1.10"
+
+            [[target]]
+            ip = {}
+            ports = 22
+            "#,
+            ip
+        ));
+        assert!(matches!(profile, Err(ScanError::Config(_))), "{}", ip);
+    }
+}
+
+#[test]
+fn excluded_hosts_and_ports_are_never_probed() {
+    let profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        exclude = { ip = "10.0.0.1", ports = { from = 23, to = 24 } }
+
+        [[target]]
+        ip = "10.0.0.0/29"
+        ports = { from = 20, to = 25 }
+        exclude = { ip = ["10.0.0.4/31", "10.0.0.6/31"], ports = 21 }
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = 53
+        protocol = "udp"
+        "#,
+    )
+    .unwrap();
+
+    let targets = &profile.scan.targets;
+    assert_eq!(
+        targets[0].ips,
+        vec![ip("10.0.0.0"), ip("10.0.0.2"), ip("10.0.0.3")]
+    );
+    assert_eq!(targets[0].ports, vec![20, 22, 25]);
+    assert!(targets[1].ips.is_empty());
+}
+
+#[test]
+fn port_strings_take_protocol_prefixes_and_open_ranges() {
+    let spec: PortSpec = "22,80, 8000-8002,U:53,161,T:3389".parse().unwrap();
+    assert_eq!(spec.ports, vec![22, 80, 8000, 8001, 8002]);
+    assert_eq!(spec.udp, vec![53, 161]);
+    assert_eq!(spec.tcp, vec![3389]);
+
+    let low: PortSpec = "-1024".parse().unwrap();
+    assert_eq!(low.ports.len(), 1024);
+    assert_eq!(low.ports[0], 1);
+    let high: PortSpec = "t:65530-".parse().unwrap();
+    assert_eq!(high.tcp, (65530..=65535).collect::<Vec<u16>>());
+
+    for invalid in ["", "80,", "100-90", "S:80", "70000", "http"] {
+        assert!(invalid.parse::<PortSpec>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn targets_and_overrides_accept_port_strings() {
+    let mut profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        exclude = { ports = "U:161" }
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = "22,U:53,161"
+        exclude = { ports = 23 }
+
+        [[target]]
+        ip = "10.0.0.2"
+        ports = "53,T:80"
+        protocol = "udp"
+        "#,
+    )
+    .unwrap();
+
+    let targets = &profile.scan.targets;
+    assert_eq!(
+        (&targets[0].ports, &targets[0].udp_ports),
+        (&vec![22], &vec![53])
+    );
+    assert_eq!(
+        (&targets[1].ports, &targets[1].udp_ports),
+        (&vec![80], &vec![53])
+    );
+
+    profile.set_ports(&"21-23,U:161-162".parse().unwrap());
+    let targets = &profile.scan.targets;
+    assert_eq!(
+        (&targets[0].ports, &targets[0].udp_ports),
+        (&vec![21, 22], &vec![162])
+    );
+    assert_eq!(
+        (&targets[1].ports, &targets[1].udp_ports),
+        (&vec![21, 22, 23], &vec![162])
+    );
+
+    let invalid = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = "22,X:53"
+        "#,
+    );
+    assert!(matches!(invalid, Err(ScanError::Config(_))));
+}
+
+#[test]
+fn top_ports_are_the_most_frequently_open_ones() {
+    let profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = { top = 5 }
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = { top = 1000000 }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(profile.scan.targets[0].ports, vec![21, 22, 23, 80, 443]);
+    assert_eq!(profile.scan.targets[1].ports, profile.services.known("tcp"));
+    assert_eq!(profile.services.name("tcp", 3389), "ms-wbt-server");
+    assert_eq!(profile.services.name("udp", 53), "unknown");
+
+    let zero = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = { top = 0 }
+        "#,
+    );
+    assert!(matches!(zero, Err(ScanError::Config(_))));
+}
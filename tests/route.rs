@@ -0,0 +1,209 @@
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use pnet::{datalink::NetworkInterface, util::MacAddr};
+use syn_port_scanner::{
+    neighbor,
+    rate_limiter::RateLimiter,
+    route,
+    simnet::{self, PortBehavior, VirtualHost, VirtualNetwork},
+    toml_parser,
+    transport::{Channel, Transport},
+    Result, ScanConfig, ScanError, Scanner,
+};
+
+const LOCAL_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 1);
+const GATEWAY_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 0xfe);
+const LOCAL_IP: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 10);
+const GATEWAY_IP: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 1);
+
+const ROUTE_TABLE: &str = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+eth0\t00000000\t0100000A\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t0000000A\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+docker0\t00000000\t010011AC\t0001\t0\t0\t0\t00000000\t0\t0\t0
+";
+
+const IPV6_ROUTE_TABLE: &str = "\
+20010db8000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe8000000000000000000000abcd0001 00000258 00000001 00000000 00000003 wlan0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000100 00000001 00000000 00000003 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000100 00000001 00000000 00000001 eth1
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo
+";
+
+const NEIGHBOR_TABLE: &str = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.168.1.1      0x1         0x2         80:05:88:77:6c:bc     *        wlan0
+192.168.1.7      0x1         0x0         00:00:00:00:00:00     *        wlan0
+";
+
+#[test]
+fn default_routes_are_read_in_metric_order() {
+    let routes = route::parse_default_routes(ROUTE_TABLE);
+
+    let routes: Vec<(&str, IpAddr)> = routes
+        .iter()
+        .map(|x| (x.interface.as_str(), x.gateway))
+        .collect();
+    // the on-link route and the one without a gateway flag are no defaults
+    assert_eq!(
+        routes,
+        vec![
+            ("eth0", Ipv4Addr::new(10, 0, 0, 1).into()),
+            ("wlan0", Ipv4Addr::new(192, 168, 1, 1).into()),
+        ]
+    );
+}
+
+#[test]
+fn ipv6_default_routes_are_read_in_metric_order() {
+    let routes = route::parse_default_ipv6_routes(IPV6_ROUTE_TABLE);
+
+    let routes: Vec<(&str, IpAddr)> = routes
+        .iter()
+        .map(|x| (x.interface.as_str(), x.gateway))
+        .collect();
+    // the prefix route, the gatewayless default and the reject route are
+    // no defaults
+    assert_eq!(
+        routes,
+        vec![
+            ("eth0", "fe80::1".parse().unwrap()),
+            ("wlan0", "fe80::abcd:1".parse().unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn neighbor_table_skips_incomplete_entries() {
+    assert_eq!(
+        route::parse_neighbor(NEIGHBOR_TABLE, Ipv4Addr::new(192, 168, 1, 1)),
+        Some(MacAddr(0x80, 0x05, 0x88, 0x77, 0x6c, 0xbc))
+    );
+    assert_eq!(
+        route::parse_neighbor(NEIGHBOR_TABLE, Ipv4Addr::new(192, 168, 1, 7)),
+        None
+    );
+}
+
+#[test]
+fn gateway_answers_arp_requests() {
+    let network = VirtualNetwork::new(simnet::interface(LOCAL_MAC, LOCAL_IP, 24), GATEWAY_MAC)
+        .host(VirtualHost::new(GATEWAY_IP).mac(GATEWAY_MAC));
+
+    let gateway = IpAddr::V4(GATEWAY_IP);
+    let missing = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 99));
+
+    let resolved =
+        neighbor::resolve(&network, &[gateway, missing], &RateLimiter::new(None)).unwrap();
+    assert_eq!(resolved.get(&gateway), Some(&GATEWAY_MAC));
+    assert!(!resolved.contains_key(&missing));
+}
+
+/// A simulated network whose neighbour cache already holds the gateway.
+struct Cached(VirtualNetwork);
+
+impl Transport for Cached {
+    fn interface(&self) -> &NetworkInterface {
+        self.0.interface()
+    }
+
+    fn open(&self) -> Result<Box<dyn Channel>> {
+        self.0.open()
+    }
+
+    fn neighbor(&self, ip: Ipv4Addr) -> Option<MacAddr> {
+        (ip == GATEWAY_IP).then_some(GATEWAY_MAC)
+    }
+}
+
+#[test]
+fn neighbors_come_from_the_transport_cache_first() {
+    // no simulated host answers ARP for the gateway
+    let network = VirtualNetwork::new(simnet::interface(LOCAL_MAC, LOCAL_IP, 24), GATEWAY_MAC);
+    let gateway = IpAddr::V4(GATEWAY_IP);
+    let neighbor = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+
+    let resolved = neighbor::resolve(
+        &Cached(network),
+        &[gateway, neighbor],
+        &RateLimiter::new(None),
+    )
+    .unwrap();
+    assert_eq!(resolved.get(&gateway), Some(&GATEWAY_MAC));
+    assert!(!resolved.contains_key(&neighbor));
+}
+
+#[test]
+fn neighbor_requests_keep_to_the_packet_rate() {
+    let ips: Vec<IpAddr> = (100..=110)
+        .map(|x| IpAddr::V4(Ipv4Addr::new(192, 168, 1, x)))
+        .collect();
+    let network = ips.iter().fold(
+        VirtualNetwork::new(simnet::interface(LOCAL_MAC, LOCAL_IP, 24), GATEWAY_MAC),
+        |network, ip| network.host(VirtualHost::new(*ip)),
+    );
+
+    let started = Instant::now();
+    let resolved = neighbor::resolve(&network, &ips, &RateLimiter::new(Some(100.0))).unwrap();
+
+    assert_eq!(resolved.len(), ips.len());
+    // the first request leaves at once, the other 10 are 10 ms apart
+    assert!(
+        started.elapsed() >= Duration::from_millis(95),
+        "{:?}",
+        started.elapsed()
+    );
+}
+
+#[test]
+fn gateway_mac_is_optional_in_profiles() {
+    let profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = 22
+        "#,
+    )
+    .unwrap();
+    assert_eq!(profile.scan.gateway_mac, None);
+
+    // the simulated interface has no route to look the gateway up from
+    let network = VirtualNetwork::new(simnet::interface(LOCAL_MAC, LOCAL_IP, 24), GATEWAY_MAC);
+    let config = ScanConfig::new(
+        LOCAL_IP,
+        None,
+        vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))],
+        vec![22],
+    );
+    let result = Scanner::with_transport(config, Arc::new(network)).run();
+    assert!(matches!(result, Err(ScanError::Interface(_))));
+}
+
+#[test]
+fn neighbors_are_scanned_without_a_gateway() {
+    let neighbor = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+    let network = || {
+        VirtualNetwork::new(simnet::interface(LOCAL_MAC, LOCAL_IP, 24), GATEWAY_MAC)
+            .host(VirtualHost::new(neighbor).port(22, PortBehavior::Open))
+    };
+
+    // there is no route to find a gateway from, and no target needs one
+    let mut config = ScanConfig::new(LOCAL_IP, None, vec![neighbor], vec![22]);
+
+    for skip_discovery in [false, true] {
+        config.skip_discovery = skip_discovery;
+        let result = Scanner::with_transport(config.clone(), Arc::new(network()))
+            .run()
+            .unwrap();
+        assert_eq!(result.open.len(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+use std::{env, fs, process};
+
+use syn_port_scanner::{config::Services, toml_parser, ScanError};
+
+const NMAP_SERVICES: &str = "\
+# Fields in this file are: Service name, portnum/protocol, open-frequency, optional comments
+#
+tcpmux\t1/tcp\t0.001995\t# TCP Port Service Multiplexer [rfc-1078]
+ssh\t22/tcp\t0.182286\t# Secure Shell Login
+domain\t53/udp\t0.213496\t# Domain Name Server
+snmp\t161/udp\t0.433467
+ntp\t123/udp
+http\t80/tcp\t0.484143\t# World Wide Web HTTP
+sgmp\t80/sctp\t0.000000
+
+http-alt\t80/tcp\t0.0001\t# shadowed by the first 80/tcp
+";
+
+#[test]
+fn nmap_services_are_keyed_on_protocol_and_ranked_by_frequency() {
+    let services: Services = NMAP_SERVICES.parse().unwrap();
+
+    let http = services.get("tcp", 80).unwrap();
+    assert_eq!(http.name, "http");
+    assert_eq!(http.frequency, Some(0.484143));
+    assert_eq!(http.comment.as_deref(), Some("World Wide Web HTTP"));
+    assert_eq!(services.get("udp", 53).unwrap().name, "domain");
+    assert_eq!(services.get("sctp", 80).unwrap().name, "sgmp");
+    assert_eq!(services.get("udp", 123).unwrap().frequency, None);
+    assert!(services.get("udp", 80).is_none());
+
+    assert_eq!(services.top("tcp", 2), [80, 22]);
+    assert_eq!(services.top("udp", 10), [161, 53, 123]);
+    assert_eq!(services.known("tcp"), [1, 22, 80]);
+    assert!(services.known("dccp").is_empty());
+
+    for invalid in [
+        "ssh 22",
+        "ssh 70000/tcp",
+        "ssh 22/tcp often",
+        "ssh 22/tcp 2.0",
+    ] {
+        assert!(invalid.parse::<Services>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn profiles_load_a_services_file() {
+    let path = env::temp_dir().join(format!("nmap-services-{}", process::id()));
+    fs::write(&path, NMAP_SERVICES).unwrap();
+
+    let profile = toml_parser::parse_str(&format!(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        services = {:?}
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = {{ top = 2 }}
+        protocol = "udp"
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = "known"
+        "#,
+        path.to_str().unwrap()
+    ))
+    .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(profile.scan.targets[0].udp_ports, vec![53, 161]);
+    assert_eq!(profile.scan.targets[1].ports, vec![1, 22, 80]);
+    assert_eq!(profile.services.name("udp", 161), "snmp");
+    assert_eq!(profile.services.get("tcp", 3389), None);
+
+    let missing = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        services = "/nonexistent/nmap-services"
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = 22
+        "#,
+    );
+    assert!(matches!(missing, Err(ScanError::Config(_))));
+}
+
+#[test]
+fn udp_known_and_top_ports_need_a_services_file() {
+    for ports in [r#""known""#, "{ top = 5 }"] {
+        let profile = toml_parser::parse_str(&format!(
+            r#"
+            [profile]
+            interface.ip = "192.168.1.10"
+
+            [[target]]
+            ip = "10.0.0.1"
+            ports = {}
+            protocol = "udp"
+            "#,
+            ports
+        ));
+        assert!(matches!(profile, Err(ScanError::Config(_))), "{}", ports);
+    }
+}
@@ -0,0 +1,648 @@
+use std::{
+    fs,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    process,
+    sync::Arc,
+    time::Duration,
+};
+
+use pnet::util::MacAddr;
+use syn_port_scanner::{
+    config::Services,
+    display,
+    simnet::{self, EchoBehavior, PortBehavior, VirtualHost, VirtualNetwork},
+    toml_parser::{self, ShowRule},
+    Discovery, PortResult, Protocol, Reason, ScanConfig, ScanError, ScanResult, ScanType, Scanner,
+};
+use toml::Table;
+
+const LOCAL_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 1);
+const GATEWAY_MAC: MacAddr = MacAddr(0x02, 0, 0, 0, 0, 0xfe);
+const LOCAL_IP: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 10);
+
+const WEB: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 1));
+const FIREWALLED: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 2));
+const DARK: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 3));
+
+fn network() -> VirtualNetwork {
+    VirtualNetwork::new(simnet::interface(LOCAL_MAC, LOCAL_IP, 24), GATEWAY_MAC)
+        .host(
+            VirtualHost::new(WEB)
+                .port(22, PortBehavior::Open)
+                .port(80, PortBehavior::Open)
+                .port(443, PortBehavior::Open),
+        )
+        .host(
+            VirtualHost::new(FIREWALLED)
+                .port(80, PortBehavior::Open)
+                .port(25, PortBehavior::Unreachable(13))
+                .default_port(PortBehavior::Dropped),
+        )
+        .host(
+            VirtualHost::new(DARK)
+                .echo(EchoBehavior::Silent)
+                .port(80, PortBehavior::Open),
+        )
+}
+
+fn scan(network: VirtualNetwork, ips: Vec<IpAddr>, ports: Vec<u16>) -> ScanResult {
+    scan_with(network, ScanConfig::new(LOCAL_IP, GATEWAY_MAC, ips, ports))
+}
+
+fn scan_with(network: VirtualNetwork, config: ScanConfig) -> ScanResult {
+    Scanner::with_transport(config, Arc::new(network))
+        .run()
+        .unwrap()
+}
+
+fn sockets(ip: IpAddr, ports: &[u16]) -> Vec<SocketAddr> {
+    ports.iter().map(|x| SocketAddr::new(ip, *x)).collect()
+}
+
+fn sorted(results: &[PortResult]) -> Vec<SocketAddr> {
+    let mut sockets: Vec<SocketAddr> = results.iter().map(|x| x.socket).collect();
+    sockets.sort();
+    sockets
+}
+
+#[test]
+fn classifies_open_closed_and_filtered_ports() {
+    let result = scan(
+        network(),
+        vec![WEB, FIREWALLED, DARK],
+        vec![22, 23, 25, 80, 443],
+    );
+
+    assert_eq!(result.reachable, vec![WEB, FIREWALLED]);
+
+    assert_eq!(
+        sorted(&result.open),
+        [sockets(WEB, &[22, 80, 443]), sockets(FIREWALLED, &[80])].concat()
+    );
+    assert_eq!(sorted(&result.closed), sockets(WEB, &[23, 25]));
+    assert_eq!(
+        sorted(&result.filtered),
+        sockets(FIREWALLED, &[22, 23, 25, 443])
+    );
+}
+
+#[test]
+fn rate_limited_host_stops_answering() {
+    let network = network().host(
+        VirtualHost::new(WEB).default_port(PortBehavior::rate_limited(
+            PortBehavior::Closed,
+            3,
+            Duration::from_secs(60),
+        )),
+    );
+
+    let result = scan(network, vec![WEB], (1..=10).collect());
+
+    assert!(result.open.is_empty());
+    assert_eq!(result.closed.len(), 3);
+    assert_eq!(result.filtered.len(), 7);
+}
+
+#[test]
+fn reordered_replies_give_the_same_result() {
+    let ports: Vec<u16> = (1..=200).collect();
+
+    let expected = scan(network(), vec![WEB, FIREWALLED], ports.clone());
+    let reordered = scan(
+        network().reorder(true).seed(42),
+        vec![WEB, FIREWALLED],
+        ports,
+    );
+
+    assert_eq!(sorted(&reordered.open), sorted(&expected.open));
+    assert_eq!(sorted(&reordered.closed), sorted(&expected.closed));
+    assert_eq!(sorted(&reordered.filtered), sorted(&expected.filtered));
+}
+
+#[test]
+fn lost_packets_only_turn_into_filtered_ports() {
+    let ports: Vec<u16> = (1..=100).collect();
+
+    let result = scan(
+        network().loss(0.2).seed(7),
+        vec![WEB, FIREWALLED],
+        ports.clone(),
+    );
+
+    for socket in sorted(&result.open) {
+        assert!(
+            [WEB, FIREWALLED].contains(&socket.ip()) && [22, 80, 443].contains(&socket.port()),
+            "{} wrongly reported open",
+            socket
+        );
+    }
+    for socket in sorted(&result.closed) {
+        assert_eq!(socket.ip(), WEB, "{} wrongly reported closed", socket);
+    }
+
+    let total = result.open.len() + result.closed.len() + result.filtered.len();
+    assert_eq!(total, result.reachable.len() * ports.len());
+}
+
+#[test]
+fn display_writes_the_scan_as_toml() {
+    let result = scan(network(), vec![WEB, FIREWALLED], vec![22, 23, 80]);
+
+    let path = std::env::temp_dir().join(format!("simnet-display-{}.toml", process::id()));
+    let show = ShowRule {
+        open: true,
+        closed: true,
+        filtered: true,
+        unfiltered: true,
+        open_filtered: true,
+    };
+    display::display(
+        &result,
+        &show,
+        &Services::embedded(),
+        path.to_string_lossy().into_owned(),
+    )
+    .unwrap();
+
+    let output: Table = fs::read_to_string(&path).unwrap().parse().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let summary = output["summary"].as_table().unwrap();
+    assert_eq!(summary["total"].as_integer(), Some(6));
+    assert_eq!(summary["open"].as_integer(), Some(3));
+    assert_eq!(summary["closed"].as_integer(), Some(1));
+    assert_eq!(summary["filtered"].as_integer(), Some(2));
+
+    let targets = output["target"].as_array().unwrap();
+    assert_eq!(targets.len(), 2);
+
+    assert_eq!(targets[0]["ip"].as_str(), Some("10.1.0.1"));
+    let web = &targets[0]["tcp"];
+    let open: Vec<(i64, &str)> = web["open"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| (x["port"].as_integer().unwrap(), x["name"].as_str().unwrap()))
+        .collect();
+    assert_eq!(open, vec![(22, "ssh"), (80, "http")]);
+    assert_eq!(web["closed"].as_array().unwrap().len(), 1);
+
+    assert_eq!(targets[1]["ip"].as_str(), Some("10.1.0.2"));
+    let firewalled = &targets[1]["tcp"];
+    assert_eq!(firewalled["filtered"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn silent_ports_are_retried_before_being_filtered() {
+    let network = network().host(
+        VirtualHost::new(WEB)
+            .port(22, PortBehavior::Open)
+            .default_port(PortBehavior::Dropped),
+    );
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB], vec![22, 80]);
+    config.timing.retries = 3;
+
+    let result = scan_with(network, config);
+
+    assert_eq!(
+        result.open,
+        vec![PortResult {
+            socket: SocketAddr::new(WEB, 22),
+            protocol: Protocol::Tcp,
+            attempts: 1,
+        }]
+    );
+    assert_eq!(
+        result.filtered,
+        vec![PortResult {
+            socket: SocketAddr::new(WEB, 80),
+            protocol: Protocol::Tcp,
+            attempts: 4,
+        }]
+    );
+}
+
+#[test]
+fn the_largest_retry_count_still_ends_the_scan() {
+    let network = network().host(VirtualHost::new(WEB).default_port(PortBehavior::Dropped));
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB], vec![80]);
+    config.timing.retries = u8::MAX;
+    config.timing.timeout = Duration::from_millis(1);
+    config.timing.min_timeout = Duration::from_millis(1);
+
+    let result = scan_with(network, config);
+
+    assert_eq!(sorted(&result.filtered), sockets(WEB, &[80]));
+    assert_eq!(result.filtered[0].attempts, u8::MAX);
+}
+
+#[test]
+fn retries_recover_ports_from_a_lossy_link() {
+    let network = network()
+        .seed(3)
+        .host(VirtualHost::new(WEB).port(80, PortBehavior::lossy(PortBehavior::Open, 0.5)));
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB], vec![80]);
+    config.timing.retries = 10;
+
+    let result = scan_with(network, config);
+
+    assert_eq!(sorted(&result.open), sockets(WEB, &[80]));
+    assert!(result.open[0].attempts <= 11);
+}
+
+#[test]
+fn udp_ports_are_classified_by_replies_and_unreachables() {
+    let network = network().host(
+        VirtualHost::new(WEB)
+            .port(53, PortBehavior::Open)
+            .udp_port(53, PortBehavior::Open)
+            .udp_port(123, PortBehavior::Dropped)
+            .udp_port(161, PortBehavior::Unreachable(13)),
+    );
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB], vec![53]);
+    config.targets[0].udp_ports = vec![53, 123, 161, 500];
+
+    let result = scan_with(network, config);
+
+    let udp = |results: &[PortResult]| {
+        results
+            .iter()
+            .filter(|x| x.protocol == Protocol::Udp)
+            .map(|x| x.socket.port())
+            .collect::<Vec<u16>>()
+    };
+    assert_eq!(udp(&result.open), vec![53]);
+    assert_eq!(udp(&result.closed), vec![500]);
+    assert_eq!(udp(&result.filtered), vec![161]);
+    assert_eq!(udp(&result.open_filtered), vec![123]);
+
+    assert_eq!(
+        result
+            .open
+            .iter()
+            .filter(|x| x.protocol == Protocol::Tcp)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn display_groups_ports_by_protocol() {
+    let network = network().host(VirtualHost::new(WEB).udp_port(53, PortBehavior::Open));
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB], vec![22]);
+    config.targets[0].udp_ports = vec![53];
+    let result = scan_with(network, config);
+
+    let services: Services = "domain\t53/udp\t0.213496\n".parse().unwrap();
+    let path = std::env::temp_dir().join(format!("simnet-protocols-{}.toml", process::id()));
+    display::display(
+        &result,
+        &ShowRule::default(),
+        &services,
+        path.to_string_lossy().into_owned(),
+    )
+    .unwrap();
+
+    let output: Table = fs::read_to_string(&path).unwrap().parse().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let web = &output["target"].as_array().unwrap()[0];
+    assert_eq!(web["tcp"]["open"].as_array().unwrap().len(), 0);
+    assert_eq!(web["udp"]["open"][0]["port"].as_integer(), Some(53));
+    assert_eq!(web["udp"]["open"][0]["name"].as_str(), Some("domain"));
+}
+
+#[test]
+fn ack_scan_tells_unfiltered_ports_from_filtered_ones() {
+    let mut config = ScanConfig::new(
+        LOCAL_IP,
+        GATEWAY_MAC,
+        vec![WEB, FIREWALLED],
+        vec![22, 25, 80],
+    );
+    config.scan_type = ScanType::Ack;
+
+    let result = scan_with(network(), config);
+
+    assert!(result.open.is_empty());
+    assert!(result.closed.is_empty());
+    assert_eq!(
+        sorted(&result.unfiltered),
+        [sockets(WEB, &[22, 25, 80]), sockets(FIREWALLED, &[80])].concat()
+    );
+    assert_eq!(sorted(&result.filtered), sockets(FIREWALLED, &[22, 25]));
+}
+
+#[test]
+fn stealth_scans_only_hear_from_closed_ports() {
+    for scan_type in [
+        ScanType::Fin,
+        ScanType::Null,
+        ScanType::Xmas,
+        ScanType::Maimon,
+    ] {
+        let mut config = ScanConfig::new(
+            LOCAL_IP,
+            GATEWAY_MAC,
+            vec![WEB, FIREWALLED],
+            vec![22, 23, 25],
+        );
+        config.scan_type = scan_type;
+        config.timing.retries = 0;
+
+        let result = scan_with(network(), config);
+
+        assert!(result.open.is_empty(), "{}", scan_type);
+        assert_eq!(
+            sorted(&result.closed),
+            sockets(WEB, &[23, 25]),
+            "{}",
+            scan_type
+        );
+        assert_eq!(
+            sorted(&result.filtered),
+            sockets(FIREWALLED, &[25]),
+            "{}",
+            scan_type
+        );
+        assert_eq!(
+            sorted(&result.open_filtered),
+            [sockets(WEB, &[22]), sockets(FIREWALLED, &[22, 23])].concat(),
+            "{}",
+            scan_type
+        );
+    }
+}
+
+#[test]
+fn window_scan_reads_the_window_of_resets() {
+    let network = network().host(
+        VirtualHost::new(WEB)
+            .port(22, PortBehavior::Open)
+            .port(80, PortBehavior::Open)
+            .rst_window(1024),
+    );
+
+    let mut config = ScanConfig::new(
+        LOCAL_IP,
+        GATEWAY_MAC,
+        vec![WEB, FIREWALLED],
+        vec![22, 23, 80],
+    );
+    config.scan_type = ScanType::Window;
+    config.timing.retries = 0;
+
+    let result = scan_with(network, config);
+
+    assert_eq!(sorted(&result.open), sockets(WEB, &[22, 80]));
+    assert_eq!(
+        sorted(&result.closed),
+        [sockets(WEB, &[23]), sockets(FIREWALLED, &[80])].concat()
+    );
+    assert_eq!(sorted(&result.filtered), sockets(FIREWALLED, &[22, 23]));
+}
+
+#[test]
+fn neighbors_that_ignore_pings_are_found_by_arp() {
+    let neighbor = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+    let neighbor_mac = MacAddr(0x02, 0x11, 0x22, 0x33, 0x44, 0x55);
+    let network = network().host(
+        VirtualHost::new(neighbor)
+            .mac(neighbor_mac)
+            .echo(EchoBehavior::Silent)
+            .port(445, PortBehavior::Open),
+    );
+
+    let result = scan(network, vec![neighbor, WEB, DARK], vec![80, 445]);
+
+    // the neighbor only sees frames addressed to its own MAC, so an open
+    // port proves the SYN skipped the gateway
+    assert_eq!(result.reachable.len(), 2);
+    assert!(result.reachable.contains(&neighbor));
+    assert_eq!(
+        sorted(&result.open),
+        vec![SocketAddr::new(WEB, 80), SocketAddr::new(neighbor, 445)]
+    );
+    assert_eq!(result.macs.get(&neighbor), Some(&neighbor_mac));
+    assert!(!result.macs.contains_key(&WEB));
+}
+
+#[test]
+fn tcp_and_udp_probes_find_hosts_that_ignore_pings() {
+    let syn_only = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 4));
+    let udp_only = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 5));
+    let network = network()
+        .host(
+            VirtualHost::new(syn_only)
+                .echo(EchoBehavior::Silent)
+                .port(443, PortBehavior::Open)
+                .default_port(PortBehavior::Dropped),
+        )
+        .host(
+            VirtualHost::new(udp_only)
+                .echo(EchoBehavior::Silent)
+                .default_port(PortBehavior::Dropped),
+        );
+
+    let mut config = ScanConfig::new(
+        LOCAL_IP,
+        GATEWAY_MAC,
+        vec![WEB, DARK, syn_only, udp_only],
+        vec![443],
+    );
+    config.discovery = Discovery {
+        echo: true,
+        syn_ports: vec![443],
+        ack_ports: vec![],
+        udp_ports: vec![40125],
+    };
+    let result = scan_with(network, config);
+
+    assert_eq!(result.reasons.get(&WEB), Some(&Reason::EchoReply));
+    assert_eq!(result.reasons.get(&syn_only), Some(&Reason::SynAck));
+    assert_eq!(result.reasons.get(&udp_only), Some(&Reason::Unreachable));
+    // DARK has 443 closed, the RST comes back before the UDP probe is sent
+    assert_eq!(result.reasons.get(&DARK), Some(&Reason::Reset));
+    assert_eq!(result.reachable.len(), 4);
+
+    // nothing gets through to a host that drops every probe
+    let network = VirtualNetwork::new(simnet::interface(LOCAL_MAC, LOCAL_IP, 24), GATEWAY_MAC)
+        .host(
+            VirtualHost::new(DARK)
+                .echo(EchoBehavior::Silent)
+                .default_port(PortBehavior::Dropped)
+                .default_udp_port(PortBehavior::Dropped),
+        );
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![DARK], vec![80]);
+    config.discovery.ack_ports = vec![80];
+    config.discovery.udp_ports = vec![40125];
+    let result = scan_with(network, config);
+    assert!(result.reachable.is_empty());
+}
+
+#[test]
+fn discovery_without_any_probe_is_rejected() {
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB], vec![80]);
+    config.discovery.echo = false;
+
+    let result = Scanner::with_transport(config, Arc::new(network())).run();
+
+    assert!(matches!(result, Err(ScanError::Config(_))));
+}
+
+#[test]
+fn profiles_configure_discovery_probes() {
+    let profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        gateway.mac = "02:00:00:00:00:fe"
+        discovery = { syn = [80, 443], ack = 80 }
+
+        [[target]]
+        ip = "10.0.0.1"
+        ports = 22
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        profile.scan.discovery,
+        Discovery {
+            echo: true,
+            syn_ports: vec![80, 443],
+            ack_ports: vec![80],
+            udp_ports: vec![],
+        }
+    );
+}
+
+#[test]
+fn skipped_discovery_scans_hosts_that_ignore_every_probe() {
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB, DARK], vec![80]);
+    config.skip_discovery = true;
+    let result = scan_with(network(), config);
+
+    assert_eq!(
+        sorted(&result.open),
+        vec![SocketAddr::new(WEB, 80), SocketAddr::new(DARK, 80)]
+    );
+    assert_eq!(result.reasons.get(&DARK), Some(&Reason::UserSet));
+}
+
+#[test]
+fn discovery_only_lists_live_hosts_without_port_scanning() {
+    let network = network();
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![WEB, FIREWALLED, DARK], vec![80]);
+    config.discovery_only = true;
+    let result = scan_with(network, config);
+
+    assert_eq!(result.reachable.len(), 2);
+    assert!(result.open.is_empty() && result.filtered.is_empty());
+
+    let path = std::env::temp_dir().join(format!("simnet-hosts-{}.toml", process::id()));
+    display::display(
+        &result,
+        &ShowRule::default(),
+        &Services::embedded(),
+        path.to_string_lossy().into_owned(),
+    )
+    .unwrap();
+
+    let output: Table = fs::read_to_string(&path).unwrap().parse().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output["summary"]["hosts_up"].as_integer(), Some(2));
+    let hosts: Vec<(&str, &str)> = output["target"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| (x["ip"].as_str().unwrap(), x["reason"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        hosts,
+        vec![("10.1.0.1", "echo-reply"), ("10.1.0.2", "echo-reply")]
+    );
+}
+
+#[test]
+fn on_link_hosts_are_resolved_when_discovery_is_skipped() {
+    let neighbor = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+    let network = network().host(
+        VirtualHost::new(neighbor)
+            .echo(EchoBehavior::Silent)
+            .port(80, PortBehavior::Open),
+    );
+
+    let mut config = ScanConfig::new(LOCAL_IP, GATEWAY_MAC, vec![neighbor, WEB], vec![80]);
+    config.skip_discovery = true;
+    let result = scan_with(network, config);
+
+    // the neighbor ignores frames sent to the gateway's MAC
+    assert_eq!(
+        sorted(&result.open),
+        vec![SocketAddr::new(WEB, 80), SocketAddr::new(neighbor, 80)]
+    );
+    assert!(result.macs.contains_key(&neighbor));
+    assert!(!result.macs.contains_key(&WEB));
+}
+
+#[test]
+fn each_target_only_scans_its_own_ports() {
+    let other = IpAddr::V4(Ipv4Addr::new(10, 1, 0, 6));
+    let network = network().host(VirtualHost::new(other));
+
+    let profile = toml_parser::parse_str(
+        r#"
+        [profile]
+        interface.ip = "192.168.1.10"
+        gateway.mac = "02:00:00:00:00:fe"
+
+        [[target]]
+        ip = "10.1.0.1"
+        ports = 22
+
+        [[target]]
+        ip = ["10.1.0.1", "10.1.0.6"]
+        ports = [22, 23]
+
+        [[target]]
+        ip = "10.1.0.6"
+        ports = 53
+        protocol = "udp"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(profile.scan.targets.len(), 3);
+
+    let mut config = profile.scan;
+    config.gateway_mac = Some(GATEWAY_MAC);
+    let network = Arc::new(network);
+    let result = Scanner::with_transport(config, network.clone())
+        .run()
+        .unwrap();
+
+    assert_eq!(sorted(&result.open), sockets(WEB, &[22]));
+    let mut closed: Vec<(Protocol, SocketAddr)> = result
+        .closed
+        .iter()
+        .map(|x| (x.protocol, x.socket))
+        .collect();
+    closed.sort();
+    assert_eq!(
+        closed,
+        vec![
+            (Protocol::Tcp, SocketAddr::new(WEB, 23)),
+            (Protocol::Tcp, SocketAddr::new(other, 22)),
+            (Protocol::Tcp, SocketAddr::new(other, 23)),
+            (Protocol::Udp, SocketAddr::new(other, 53)),
+        ]
+    );
+    // two pings, one probe per socket and a reset after the open port
+    assert_eq!(network.probes(), 2 + 5 + 1);
+}
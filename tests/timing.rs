@@ -0,0 +1,88 @@
+use std::{
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+use syn_port_scanner::{
+    rate_limiter::RateLimiter,
+    timing::{RttEstimator, RttTable, Timing, TimingTemplate},
+};
+
+const MS: Duration = Duration::from_millis(1);
+
+#[test]
+fn estimator_follows_rfc6298() {
+    let timing = Timing {
+        min_timeout: MS,
+        max_timeout: MS * 10_000,
+        ..Timing::default()
+    };
+    let mut rtt = RttEstimator::default();
+
+    assert_eq!(rtt.timeout(&timing), timing.timeout);
+
+    rtt.sample(MS * 100);
+    assert_eq!(rtt.srtt(), Some(MS * 100));
+    assert_eq!(rtt.timeout(&timing), MS * 300);
+
+    rtt.sample(MS * 20);
+    assert_eq!(rtt.srtt(), Some(MS * 90));
+    assert_eq!(rtt.timeout(&timing), MS * 320);
+}
+
+#[test]
+fn table_clamps_and_falls_back_to_global_estimate() {
+    let timing = Timing::default();
+    let mut table = RttTable::new(timing);
+    let near: IpAddr = "10.0.0.1".parse().unwrap();
+    let far: IpAddr = "10.0.0.2".parse().unwrap();
+
+    assert_eq!(table.timeout(near), timing.timeout);
+
+    table.sample(near, Duration::from_micros(50));
+    assert_eq!(table.timeout(near), timing.min_timeout);
+    assert_eq!(table.timeout(far), timing.min_timeout);
+
+    table.sample(far, Duration::from_secs(5));
+    assert_eq!(table.timeout(far), timing.max_timeout);
+    assert_eq!(table.timeout(near), timing.min_timeout);
+}
+
+#[test]
+fn rate_limiter_paces_packets() {
+    let limiter = RateLimiter::new(Some(200.0));
+    let started = Instant::now();
+
+    for _ in 0..21 {
+        limiter.acquire();
+    }
+
+    // the first packet leaves at once, the other 20 are 5 ms apart
+    assert!(started.elapsed() >= MS * 95, "{:?}", started.elapsed());
+}
+
+#[test]
+fn templates_parse_by_name_and_number() {
+    assert_eq!("insane".parse(), Ok(TimingTemplate::Insane));
+    assert_eq!("T2".parse(), Ok(TimingTemplate::Polite));
+    assert_eq!("0".parse(), Ok(TimingTemplate::Paranoid));
+    assert!("warp".parse::<TimingTemplate>().is_err());
+    for name in ["ttt3", "tinsane", "t", "T6"] {
+        assert!(name.parse::<TimingTemplate>().is_err(), "{}", name);
+    }
+
+    assert_eq!(
+        TimingTemplate::Normal.timing().retries,
+        Timing::default().retries
+    );
+    assert!(TimingTemplate::Polite.timing().rate.is_some());
+}
+
+#[test]
+fn attempts_never_overflow_the_counter() {
+    let mut timing = Timing::default();
+    assert_eq!(timing.attempts(), timing.retries + 1);
+
+    timing.retries = u8::MAX;
+    assert_eq!(timing.attempts(), u8::MAX);
+}